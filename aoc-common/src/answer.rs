@@ -0,0 +1,73 @@
+use std::fmt;
+
+/*
+What a part returns: printable as the answer the puzzle asks for, plus named
+diagnostics of how it was found (the noun and verb, the best phase settings,
+the crossing that was closest, ...). Plain numbers and strings have none.
+
+Diagnostics are for people and scripts looking at a run, answer checks
+(examples, answers.txt) only compare the printed answer.
+*/
+pub trait Answer: fmt::Display {
+    fn diagnostics(&self) -> Vec<(&'static str, Value)> {
+        Vec::new()
+    }
+}
+
+macro_rules! plain_answers {
+    ($($ty:ty),*) => {
+        $(impl Answer for $ty {})*
+    };
+}
+
+plain_answers!(i32, i64, i128, u32, u64, u128, usize, String);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Number(i64),
+    Numbers(Vec<i64>),
+    Text(String),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Number(number) => write!(f, "{number}"),
+            Value::Numbers(numbers) => {
+                let numbers: Vec<String> = numbers.iter().map(i64::to_string).collect();
+                write!(f, "{}", numbers.join(","))
+            }
+            Value::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+impl From<i64> for Value {
+    fn from(number: i64) -> Self {
+        Value::Number(number)
+    }
+}
+
+impl From<i32> for Value {
+    fn from(number: i32) -> Self {
+        Value::Number(number.into())
+    }
+}
+
+impl From<u32> for Value {
+    fn from(number: u32) -> Self {
+        Value::Number(number.into())
+    }
+}
+
+impl From<Vec<i64>> for Value {
+    fn from(numbers: Vec<i64>) -> Self {
+        Value::Numbers(numbers)
+    }
+}
+
+impl From<String> for Value {
+    fn from(text: String) -> Self {
+        Value::Text(text)
+    }
+}
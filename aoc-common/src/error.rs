@@ -0,0 +1,60 @@
+use std::fmt;
+
+/*
+A parse failure pointing at the offending token:
+
+    day03/input.txt:2:17: invalid direction "X12": expected U, D, L or R
+
+Parsers only see the input text, the file name is attached afterwards by
+whoever read the file (see `input::parse_file`).
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub token: String,
+    pub reason: String,
+}
+
+impl ParseError {
+    /// `token` must be a slice of `input`, its position is derived from
+    /// where it sits in there.
+    pub fn at(input: &str, token: &str, reason: impl Into<String>) -> Self {
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| offset <= input.len())
+            .unwrap_or(0);
+        let before = &input[..offset];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        let column = before[line_start..].chars().count() + 1;
+        ParseError {
+            file: None,
+            line,
+            column,
+            token: token.to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    pub fn in_file(mut self, file: &str) -> Self {
+        self.file = Some(file.to_string());
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{file}:")?;
+        }
+        write!(
+            f,
+            "{}:{}: invalid token {:?}: {}",
+            self.line, self.column, self.token, self.reason
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
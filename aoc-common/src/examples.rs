@@ -0,0 +1,250 @@
+use crate::solution::{normalize_answer, Part, Solution};
+use anyhow::{anyhow, bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/*
+Example inputs with their expected answers, one fixture per file in
+dayNN/examples/:
+
+    part1: 6
+    part2: 30
+    ---
+    R8,U5,L5,D3
+    U7,R6,D4,L4
+
+Everything above `---` is the header, everything below is the input as-is.
+Header lines starting with `#` are comments.
+A part without a `partN:` line is not checked against that example (day06
+uses different examples for its two parts). Multi-line answers (rendered
+text) continue on the following lines, each prefixed with `|`:
+
+    part2:
+    | #..#
+    | ####
+    ---
+
+Instead of repeating the input, the header can point at a code block of the
+downloaded puzzle description with `input: puzzle.md#2` (1-based). Such
+examples are skipped when puzzle.md has not been downloaded, as the puzzle
+text is not checked in.
+*/
+
+const SEPARATOR: &str = "---";
+const PUZZLE_FILE: &str = "puzzle.md";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Example {
+    pub fn expected(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+}
+
+/// The contents of every fenced (```) or indented code block, in order.
+pub fn code_blocks(markdown: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut fenced: Option<Vec<&str>> = None;
+    let mut indented: Vec<&str> = Vec::new();
+    for line in markdown.lines() {
+        if let Some(block) = fenced.as_mut() {
+            if line.trim_start().starts_with("```") {
+                blocks.push(block.join("\n"));
+                fenced = None;
+            } else {
+                block.push(line);
+            }
+        } else if line.trim_start().starts_with("```") {
+            fenced = Some(Vec::new());
+        } else if let Some(code) = line.strip_prefix("    ") {
+            indented.push(code);
+        } else if !indented.is_empty() {
+            blocks.push(indented.join("\n"));
+            indented.clear();
+        }
+    }
+    if !indented.is_empty() {
+        blocks.push(indented.join("\n"));
+    }
+    blocks
+}
+
+/// Parses one fixture. `None` means it points into a puzzle.md that is not
+/// there.
+fn parse_fixture(name: &str, text: &str, day_dir: &Path) -> Result<Option<Example>> {
+    let mut lines = text.lines();
+    let mut answers: [Option<String>; 2] = [None, None];
+    let mut puzzle_block = None;
+    let mut current: Option<usize> = None;
+
+    for line in lines.by_ref() {
+        if line.trim_end() == SEPARATOR {
+            break;
+        }
+        if let Some(continued) = line.strip_prefix('|') {
+            let Some(answer) = current.and_then(|index| answers[index].as_mut()) else {
+                bail!("{name}: continuation line without an answer before it");
+            };
+            if !answer.is_empty() {
+                answer.push('\n');
+            }
+            answer.push_str(continued.strip_prefix(' ').unwrap_or(continued));
+            continue;
+        }
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = line
+            .split_once(':')
+            .ok_or_else(|| anyhow!("{name}: expected <key>: <value>, got {line:?}"))?;
+        let value = value.trim().to_string();
+        current = None;
+        match key.trim() {
+            "part1" | "part2" => {
+                let index = if key.trim() == "part1" { 0 } else { 1 };
+                answers[index] = Some(value);
+                current = Some(index);
+            }
+            "input" => {
+                let index = value
+                    .strip_prefix(PUZZLE_FILE)
+                    .and_then(|block| block.strip_prefix('#'))
+                    .and_then(|block| block.parse::<usize>().ok())
+                    .filter(|&block| block > 0)
+                    .ok_or_else(|| anyhow!("{name}: expected input: {PUZZLE_FILE}#<block>"))?;
+                puzzle_block = Some(index);
+            }
+            other => bail!("{name}: unknown key {other:?}"),
+        }
+    }
+
+    let input = match puzzle_block {
+        None => lines.map(|line| format!("{line}\n")).collect(),
+        Some(index) => {
+            let Ok(markdown) = fs::read_to_string(day_dir.join(PUZZLE_FILE)) else {
+                return Ok(None);
+            };
+            let blocks = code_blocks(&markdown);
+            let block = blocks.get(index - 1).ok_or_else(|| {
+                anyhow!(
+                    "{name}: {PUZZLE_FILE} has only {} code blocks",
+                    blocks.len()
+                )
+            })?;
+            format!("{block}\n")
+        }
+    };
+
+    let [part1, part2] = answers;
+    Ok(Some(Example {
+        name: name.to_string(),
+        input,
+        part1,
+        part2,
+    }))
+}
+
+/// All fixtures in `dir`, sorted by file name.
+pub fn load_examples(dir: &Path) -> Result<Vec<Example>> {
+    let day_dir = dir.parent().unwrap_or(dir);
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .with_context(|| format!("Could not read {}", dir.display()))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<_, _>>()?;
+    paths.sort();
+
+    let mut examples = Vec::new();
+    for path in paths.iter().filter(|path| path.is_file()) {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Could not read {}", path.display()))?;
+        match parse_fixture(&name, &text, day_dir)? {
+            Some(example) => examples.push(example),
+            None => eprintln!("skipping {name}: {PUZZLE_FILE} not downloaded"),
+        }
+    }
+    Ok(examples)
+}
+
+/// Runs `part` of `S` on every example in `dir` that has an expected answer
+/// for it. Fails on the first wrong answer, and when there is no example at
+/// all so a day can't silently go untested.
+pub fn check_examples<S: Solution>(dir: &Path, part: Part) -> Result<()> {
+    let mut checked = 0;
+    for example in load_examples(dir)? {
+        let Some(expected) = example.expected(part) else {
+            continue;
+        };
+        let input = S::parse(&example.input).with_context(|| example.name.clone())?;
+        let answer = match part {
+            Part::One => S::part1(&input).map(|answer| answer.to_string()),
+            Part::Two => S::part2(&input).map(|answer| answer.to_string()),
+        }
+        .with_context(|| example.name.clone())?;
+        if normalize_answer(&answer) != normalize_answer(expected) {
+            bail!(
+                "{}: part {} answered\n{answer}\nexpected\n{expected}",
+                example.name,
+                part.number()
+            );
+        }
+        checked += 1;
+    }
+    if checked == 0 {
+        bail!(
+            "No examples for day {} part {} in {}",
+            S::DAY,
+            part.number(),
+            dir.display()
+        );
+    }
+    Ok(())
+}
+
+/// One test per part that checks `$solution` against the fixtures in the
+/// calling crate's examples/ directory. Used from each day's
+/// tests/examples.rs. With `pending` (what `aoc new` generates) the tests
+/// are ignored until the example answers are filled in.
+#[macro_export]
+macro_rules! example_tests {
+    (@tests $solution:ty $(, #[$attr:meta])?) => {
+        fn examples_dir() -> ::std::path::PathBuf {
+            ::std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("examples")
+        }
+
+        #[test]
+        $(#[$attr])?
+        fn part1_examples() {
+            if let Err(err) =
+                $crate::examples::check_examples::<$solution>(&examples_dir(), $crate::Part::One)
+            {
+                panic!("{err:#}");
+            }
+        }
+
+        #[test]
+        $(#[$attr])?
+        fn part2_examples() {
+            if let Err(err) =
+                $crate::examples::check_examples::<$solution>(&examples_dir(), $crate::Part::Two)
+            {
+                panic!("{err:#}");
+            }
+        }
+    };
+    ($solution:ty, pending) => {
+        $crate::example_tests!(@tests $solution, #[ignore = "no example answers yet"]);
+    };
+    ($solution:ty) => {
+        $crate::example_tests!(@tests $solution);
+    };
+}
@@ -0,0 +1,178 @@
+use crate::error::ParseError;
+use crate::solution::Solution;
+use anyhow::{bail, Context, Result};
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+pub const INPUT_FILE: &str = "input.txt";
+pub const EXAMPLE_FILE: &str = "example.txt";
+
+/// Input paths for the day binaries when none are passed as arguments,
+/// separated like `PATH` to run several inputs in one go.
+pub const INPUT_ENV: &str = "AOC_INPUT";
+/// Stands for standard input wherever an input path is expected.
+pub const STDIN: &str = "-";
+
+/// The input.txt next to the calling crate's Cargo.toml, so day binaries
+/// find their input from any directory.
+#[macro_export]
+macro_rules! default_input {
+    () => {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")
+    };
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Source {
+    pub fn from_arg(arg: &str) -> Self {
+        if arg == STDIN {
+            Source::Stdin
+        } else {
+            Source::File(PathBuf::from(arg))
+        }
+    }
+
+    pub fn read(&self) -> Result<String> {
+        match self {
+            Source::Stdin => {
+                let mut contents = String::new();
+                io::stdin()
+                    .read_to_string(&mut contents)
+                    .context("Could not read stdin")?;
+                Ok(contents)
+            }
+            Source::File(path) => read_input(&path.to_string_lossy()),
+        }
+    }
+
+    /// For inputs too large to be read at once.
+    pub fn open(&self) -> Result<Box<dyn BufRead>> {
+        match self {
+            Source::Stdin => Ok(Box::new(io::stdin().lock())),
+            Source::File(path) => {
+                let file = fs::File::open(path)
+                    .with_context(|| format!("Could not read {}", path.display()))?;
+                Ok(Box::new(BufReader::new(file)))
+            }
+        }
+    }
+
+    /// Reads and parses the input for `S`, naming the source in parse errors.
+    pub fn parse<S: Solution>(&self) -> Result<S::Input> {
+        parse_contents::<S>(&self.read()?, &self.to_string())
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Stdin => write!(f, "<stdin>"),
+            Source::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// The inputs named in `args`, else the ones in `$AOC_INPUT`, else `default`.
+pub fn sources(args: impl IntoIterator<Item = String>, default: &str) -> Vec<Source> {
+    let args: Vec<Source> = args.into_iter().map(|arg| Source::from_arg(&arg)).collect();
+    if !args.is_empty() {
+        return args;
+    }
+    match std::env::var_os(INPUT_ENV) {
+        Some(paths) if !paths.is_empty() => std::env::split_paths(&paths)
+            .map(|path| Source::from_arg(&path.to_string_lossy()))
+            .collect(),
+        _ => vec![Source::from_arg(default)],
+    }
+}
+
+/// Parses every source for `S` and hands it to `solve`. With several sources
+/// each one's output is preceded by its name.
+pub fn for_each_input<S: Solution>(
+    sources: &[Source],
+    mut solve: impl FnMut(&S::Input) -> Result<()>,
+) -> Result<()> {
+    for_each_source(sources, |_, source| solve(&source.parse::<S>()?))
+}
+
+/// Hands every source and its index to `solve` unread, for binaries that
+/// stream their inputs or write files per input. With several sources each
+/// one's output is preceded by its name.
+pub fn for_each_source(
+    sources: &[Source],
+    mut solve: impl FnMut(usize, &Source) -> Result<()>,
+) -> Result<()> {
+    for (index, source) in sources.iter().enumerate() {
+        if sources.len() > 1 {
+            if index > 0 {
+                println!();
+            }
+            println!("{source}:");
+        }
+        solve(index, source)?;
+    }
+    Ok(())
+}
+
+/// Where each of `sources` writes the output file `path`: `path` itself for
+/// a single source, else with the input's name added, so `out.svg` becomes
+/// `out-input.svg` for `input.txt` and `out-stdin.svg` for standard input.
+/// Fails if two inputs would write the same file.
+pub fn output_paths(path: &str, sources: &[Source]) -> Result<Vec<PathBuf>> {
+    if sources.len() <= 1 {
+        return Ok(vec![PathBuf::from(path); sources.len()]);
+    }
+    let path = Path::new(path);
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let mut paths: Vec<PathBuf> = Vec::with_capacity(sources.len());
+    for source in sources {
+        let input = match source {
+            Source::Stdin => "stdin".into(),
+            Source::File(input) => input.file_stem().unwrap_or_default().to_string_lossy(),
+        };
+        let mut name = format!("{stem}-{input}");
+        if let Some(extension) = path.extension() {
+            name = format!("{name}.{}", extension.to_string_lossy());
+        }
+        let output = path.with_file_name(name);
+        if let Some(index) = paths.iter().position(|other| *other == output) {
+            bail!(
+                "{} and {source} would both be written to {}",
+                sources[index],
+                output.display()
+            );
+        }
+        paths.push(output);
+    }
+    Ok(paths)
+}
+
+pub fn read_input(file_path: &str) -> Result<String> {
+    fs::read_to_string(file_path).with_context(|| format!("Could not read {file_path}"))
+}
+
+/// Reads and parses `file_path` for `S`, naming the file in parse errors.
+pub fn parse_file<S: Solution>(file_path: &str) -> Result<S::Input> {
+    parse_contents::<S>(&read_input(file_path)?, file_path)
+}
+
+/// Parses already loaded `contents`, naming `file_path` in parse errors.
+pub fn parse_contents<S: Solution>(contents: &str, file_path: &str) -> Result<S::Input> {
+    S::parse(contents).map_err(|err| in_file(err, file_path))
+}
+
+/// Names `file_path` in `err`: parse errors get it as their location, any
+/// other error as context.
+pub fn in_file(err: anyhow::Error, file_path: &str) -> anyhow::Error {
+    match err.downcast::<ParseError>() {
+        Ok(err) => err.in_file(file_path).into(),
+        Err(err) => err.context(format!("Could not parse {file_path}")),
+    }
+}
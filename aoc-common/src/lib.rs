@@ -0,0 +1,21 @@
+/*
+Shared by every dayNN crate: input loading, parsing helpers, the `Solution`
+trait, example fixtures and timing. Improvements here land in all days at once.
+*/
+
+pub mod answer;
+pub mod error;
+pub mod examples;
+pub mod input;
+pub mod parse;
+pub mod solution;
+pub mod timing;
+
+pub use answer::{Answer, Value};
+pub use error::ParseError;
+pub use input::{
+    for_each_input, for_each_source, output_paths, parse_contents, parse_file, read_input, sources,
+    Source, EXAMPLE_FILE, INPUT_ENV, INPUT_FILE,
+};
+pub use solution::{normalize_answer, Entry, Part, PartResult, Solution};
+pub use timing::timed;
@@ -0,0 +1,240 @@
+use crate::error::ParseError;
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/*
+Parser combinators for the puzzle formats. A parser turns a token, which is
+a slice of the whole input, into a typed value. Failures point at the token
+that is wrong (see `ParseError::at`), however deeply the parsers are nested:
+
+    lines_of(separated_by(",", direction_count(&[("U", Up), ...], from_str())))
+
+    day03/input.txt:2:17: invalid token "X12": expected one of U, D, L, R
+
+Parsers ignore the whitespace around their token and `lines_of` skips blank
+lines, editors and downloads don't agree on trailing newlines.
+*/
+
+pub trait Parser<T> {
+    /// `token` must be a slice of `input`, errors are located by where it
+    /// sits in there.
+    fn parse_token(&self, input: &str, token: &str) -> Result<T, ParseError>;
+
+    fn parse(&self, input: &str) -> Result<T, ParseError> {
+        self.parse_token(input, input)
+    }
+
+    fn map<U>(self, f: impl Fn(T) -> U) -> impl Parser<U>
+    where
+        Self: Sized,
+    {
+        move |input: &str, token: &str| self.parse_token(input, token).map(&f)
+    }
+
+    /// Checks the parsed value, a rejection is reported at its token.
+    fn try_map<U>(self, f: impl Fn(T) -> Result<U, String>) -> impl Parser<U>
+    where
+        Self: Sized,
+    {
+        move |input: &str, token: &str| {
+            let value = self.parse_token(input, token)?;
+            f(value).map_err(|reason| ParseError::at(input, token.trim(), reason))
+        }
+    }
+}
+
+impl<T, F> Parser<T> for F
+where
+    F: Fn(&str, &str) -> Result<T, ParseError>,
+{
+    fn parse_token(&self, input: &str, token: &str) -> Result<T, ParseError> {
+        self(input, token)
+    }
+}
+
+/// Any `FromStr` type, e.g. a number.
+pub fn from_str<T>() -> impl Parser<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    |input: &str, token: &str| self::token(input, token.trim())
+}
+
+/// A name made of letters and digits, like `COM` or `B12`.
+pub fn word() -> impl Parser<String> {
+    |input: &str, token: &str| {
+        let token = token.trim();
+        if token.is_empty() || !token.chars().all(char::is_alphanumeric) {
+            return Err(ParseError::at(
+                input,
+                token,
+                "expected a name of letters and digits",
+            ));
+        }
+        Ok(token.to_string())
+    }
+}
+
+/// `item` between every `separator`, e.g. `1,2,3`.
+pub fn separated_by<T>(separator: &'static str, item: impl Parser<T>) -> impl Parser<Vec<T>> {
+    move |input: &str, token: &str| {
+        token
+            .trim()
+            .split(separator)
+            .map(|part| item.parse_token(input, part))
+            .collect()
+    }
+}
+
+/// One `item` per non-blank line.
+pub fn lines_of<T>(item: impl Parser<T>) -> impl Parser<Vec<T>> {
+    move |input: &str, token: &str| {
+        token
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| item.parse_token(input, line))
+            .collect()
+    }
+}
+
+/// `<left><separator><right>`, split at the first separator, e.g. `A)B`.
+pub fn pair<A, B>(
+    separator: &'static str,
+    left: impl Parser<A>,
+    right: impl Parser<B>,
+) -> impl Parser<(A, B)> {
+    move |input: &str, token: &str| {
+        let token = token.trim();
+        let (a, b) = token
+            .split_once(separator)
+            .ok_or_else(|| ParseError::at(input, token, format!("expected <a>{separator}<b>")))?;
+        Ok((left.parse_token(input, a)?, right.parse_token(input, b)?))
+    }
+}
+
+/// `<lower>-<upper>`, both ends inclusive. Bounds can't be negative, the
+/// first `-` separates them.
+pub fn range<T: PartialOrd>(bound: impl Parser<T>) -> impl Parser<RangeInclusive<T>> {
+    move |input: &str, token: &str| {
+        let token = token.trim();
+        let (lower, upper) = token
+            .split_once('-')
+            .ok_or_else(|| ParseError::at(input, token, "expected <lower>-<upper>"))?;
+        let (lower, upper) = (
+            bound.parse_token(input, lower)?,
+            bound.parse_token(input, upper)?,
+        );
+        if lower > upper {
+            return Err(ParseError::at(
+                input,
+                token,
+                "lower bound is above the upper bound",
+            ));
+        }
+        Ok(lower..=upper)
+    }
+}
+
+/// A direction followed by a count, like `R75` or `NE3`. `directions` maps
+/// the spellings to values, the longest one that matches wins.
+pub fn direction_count<D: Copy, N>(
+    directions: &'static [(&'static str, D)],
+    count: impl Parser<N>,
+) -> impl Parser<(D, N)> {
+    move |input: &str, token: &str| {
+        let token = token.trim();
+        let (name, direction) = directions
+            .iter()
+            .filter(|(name, _)| token.starts_with(name))
+            .max_by_key(|(name, _)| name.len())
+            .ok_or_else(|| {
+                let names: Vec<&str> = directions.iter().map(|(name, _)| *name).collect();
+                ParseError::at(
+                    input,
+                    token,
+                    format!("expected one of {}", names.join(", ")),
+                )
+            })?;
+        Ok((*direction, count.parse_token(input, &token[name.len()..])?))
+    }
+}
+
+/// A rectangle of characters, row by row, `y` grows downwards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<C> {
+    pub width: usize,
+    pub height: usize,
+    pub cells: Vec<C>,
+}
+
+impl<C> Grid<C> {
+    pub fn get(&self, x: usize, y: usize) -> Option<&C> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[C]> {
+        self.cells.chunks(self.width.max(1))
+    }
+}
+
+/// One row per non-blank line, `cell` says what a character stands for and
+/// rejects the ones that don't belong. All rows must be equally long.
+pub fn grid<C>(cell: impl Fn(char) -> Option<C>) -> impl Parser<Grid<C>> {
+    move |input: &str, token: &str| {
+        let mut grid = Grid {
+            width: 0,
+            height: 0,
+            cells: Vec::new(),
+        };
+        for line in token.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let width = line.chars().count();
+            if grid.height > 0 && width != grid.width {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    format!("row is {width} wide, the rows above are {}", grid.width),
+                ));
+            }
+            for (index, c) in line.char_indices() {
+                let value = cell(c).ok_or_else(|| {
+                    ParseError::at(input, &line[index..index + c.len_utf8()], "unexpected cell")
+                })?;
+                grid.cells.push(value);
+            }
+            grid.width = width;
+            grid.height += 1;
+        }
+        Ok(grid)
+    }
+}
+
+/// Parses a single token, reporting where it sits in `input` on failure.
+pub fn token<T>(input: &str, token: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    token
+        .parse()
+        .map_err(|err: T::Err| ParseError::at(input, token, err.to_string()))
+}
+
+/// `1,2,3` (e.g. an Intcode program).
+pub fn comma_separated<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    separated_by(",", from_str()).parse(input)
+}
+
+/// One value per non-blank line.
+pub fn lines<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    lines_of(from_str()).parse(input)
+}
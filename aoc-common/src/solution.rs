@@ -0,0 +1,132 @@
+use crate::answer::{Answer, Value};
+use crate::timing::timed;
+use anyhow::{anyhow, Result};
+use std::any::Any;
+use std::time::Duration;
+
+/*
+One puzzle day: the input is parsed once and both parts work on the parsed
+value. The answers are typed per part (a count, a rendered image, ...) and
+printable, optionally with diagnostics (see `Answer`).
+*/
+pub trait Solution {
+    const DAY: u8;
+
+    type Input;
+    type Answer1: Answer;
+    type Answer2: Answer;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Self::Answer1>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer2>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    pub diagnostics: Vec<(&'static str, Value)>,
+    pub elapsed: Duration,
+}
+
+fn report(answer: &impl Answer) -> (String, Vec<(&'static str, Value)>) {
+    (answer.to_string(), answer.diagnostics())
+}
+
+/// Trailing whitespace is not significant, rendered answers pad their rows
+/// and editors strip it from fixtures and answer files.
+pub fn normalize_answer(answer: &str) -> String {
+    answer
+        .trim_end()
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
+/// Parses `input` once and runs the requested `parts`, timing each of them.
+pub fn run<S: Solution>(input: &str, parts: &[Part]) -> Result<Vec<PartResult>> {
+    let parsed = S::parse(input)?;
+    parts
+        .iter()
+        .map(|&part| {
+            let (answer, elapsed) = timed(|| match part {
+                Part::One => S::part1(&parsed).map(|answer| report(&answer)),
+                Part::Two => S::part2(&parsed).map(|answer| report(&answer)),
+            });
+            let (answer, diagnostics) = answer?;
+            Ok(PartResult {
+                day: S::DAY,
+                part,
+                answer,
+                diagnostics,
+                elapsed,
+            })
+        })
+        .collect()
+}
+
+fn parse_any<S: Solution>(input: &str) -> Result<Box<dyn Any>>
+where
+    S::Input: 'static,
+{
+    Ok(Box::new(S::parse(input)?))
+}
+
+fn solve_any<S: Solution>(input: &dyn Any, part: Part) -> Result<String>
+where
+    S::Input: 'static,
+{
+    let input = input
+        .downcast_ref::<S::Input>()
+        .ok_or_else(|| anyhow!("Input was not parsed by day {}", S::DAY))?;
+    match part {
+        Part::One => S::part1(input).map(|answer| answer.to_string()),
+        Part::Two => S::part2(input).map(|answer| answer.to_string()),
+    }
+}
+
+/*
+Type-erased `Solution`, so a runner can keep all days in one list. `run` does
+a whole day; `parse` and `solve` expose the stages separately for the
+benchmarks, `solve` only accepts what this entry's `parse` returned.
+*/
+#[derive(Clone, Copy)]
+pub struct Entry {
+    pub day: u8,
+    pub run: fn(&str, &[Part]) -> Result<Vec<PartResult>>,
+    pub parse: fn(&str) -> Result<Box<dyn Any>>,
+    pub solve: fn(&dyn Any, Part) -> Result<String>,
+}
+
+impl Entry {
+    pub fn of<S: Solution>() -> Self
+    where
+        S::Input: 'static,
+    {
+        Entry {
+            day: S::DAY,
+            run: run::<S>,
+            parse: parse_any::<S>,
+            solve: solve_any::<S>,
+        }
+    }
+}
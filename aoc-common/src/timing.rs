@@ -0,0 +1,8 @@
+use std::time::{Duration, Instant};
+
+/// Runs `f` and returns its result together with the wall-clock time it took.
+pub fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
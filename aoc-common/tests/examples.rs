@@ -0,0 +1,56 @@
+use aoc_common::examples::{code_blocks, load_examples};
+use std::fs;
+
+const PUZZLE: &str = "\
+For example:
+
+```
+R8,U5,L5,D3
+U7,R6,D4,L4
+```
+
+and then
+
+    COM)B
+    B)C
+
+That's it.
+";
+
+#[test]
+fn code_blocks_finds_fenced_and_indented_blocks() {
+    assert_eq!(
+        code_blocks(PUZZLE),
+        vec!["R8,U5,L5,D3\nU7,R6,D4,L4", "COM)B\nB)C"]
+    );
+}
+
+#[test]
+fn fixtures_can_take_their_input_from_puzzle_md() {
+    let day_dir = std::env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+    let examples_dir = day_dir.join("examples");
+    fs::create_dir_all(&examples_dir).unwrap();
+    fs::write(
+        examples_dir.join("a.txt"),
+        "part1: 6\npart2:\n| two\n| lines\n---\n1,2\n",
+    )
+    .unwrap();
+    fs::write(
+        examples_dir.join("b.txt"),
+        "part1: 42\ninput: puzzle.md#2\n",
+    )
+    .unwrap();
+
+    // Without puzzle.md the second fixture is skipped.
+    assert_eq!(load_examples(&examples_dir).unwrap().len(), 1);
+
+    fs::write(day_dir.join("puzzle.md"), PUZZLE).unwrap();
+    let examples = load_examples(&examples_dir).unwrap();
+    fs::remove_dir_all(&day_dir).unwrap();
+
+    assert_eq!(examples[0].input, "1,2\n");
+    assert_eq!(examples[0].part2.as_deref(), Some("two\nlines"));
+    assert_eq!(examples[1].input, "COM)B\nB)C\n");
+    assert_eq!(examples[1].part1.as_deref(), Some("42"));
+    assert_eq!(examples[1].part2, None);
+}
@@ -0,0 +1,45 @@
+use aoc_common::{output_paths, Source};
+use std::path::PathBuf;
+
+#[test]
+fn a_single_input_writes_the_path_as_given() {
+    let inputs = [Source::from_arg("day03/input.txt")];
+    assert_eq!(
+        output_paths("wires.svg", &inputs).unwrap(),
+        [PathBuf::from("wires.svg")]
+    );
+}
+
+#[test]
+fn several_inputs_write_files_named_after_them() {
+    let inputs = [
+        Source::from_arg("inputs/mine.txt"),
+        Source::from_arg("-"),
+        Source::from_arg("friend"),
+    ];
+    assert_eq!(
+        output_paths("out/wires.svg", &inputs).unwrap(),
+        [
+            PathBuf::from("out/wires-mine.svg"),
+            PathBuf::from("out/wires-stdin.svg"),
+            PathBuf::from("out/wires-friend.svg"),
+        ]
+    );
+    assert_eq!(
+        output_paths("record", &inputs[..2]).unwrap(),
+        [PathBuf::from("record-mine"), PathBuf::from("record-stdin")]
+    );
+}
+
+#[test]
+fn inputs_with_the_same_name_are_rejected() {
+    let inputs = [
+        Source::from_arg("a/input.txt"),
+        Source::from_arg("b/input.txt"),
+    ];
+    let err = output_paths("wires.svg", &inputs).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "a/input.txt and b/input.txt would both be written to wires-input.svg"
+    );
+}
@@ -0,0 +1,96 @@
+use aoc_common::parse::{
+    direction_count, from_str, grid, lines_of, pair, range, separated_by, word, Parser,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    North,
+    NorthEast,
+    East,
+}
+
+const DIRECTIONS: &[(&str, Direction)] = &[
+    ("N", Direction::North),
+    ("NE", Direction::NorthEast),
+    ("E", Direction::East),
+];
+
+#[test]
+fn nested_parsers_build_typed_values() {
+    let moves = lines_of(separated_by(
+        ",",
+        direction_count(DIRECTIONS, from_str::<u32>()),
+    ));
+    assert_eq!(
+        moves.parse("N3,NE12\n\nE1\n").unwrap(),
+        vec![
+            vec![(Direction::North, 3), (Direction::NorthEast, 12)],
+            vec![(Direction::East, 1)],
+        ]
+    );
+    assert_eq!(
+        lines_of(pair(")", word(), word()))
+            .parse("COM)B\nB)C")
+            .unwrap(),
+        vec![
+            ("COM".to_string(), "B".to_string()),
+            ("B".to_string(), "C".to_string())
+        ]
+    );
+    assert_eq!(range(from_str::<u32>()).parse("10-20\n").unwrap(), 10..=20);
+}
+
+#[test]
+fn errors_point_at_the_innermost_token() {
+    let moves = lines_of(separated_by(
+        ",",
+        direction_count(DIRECTIONS, from_str::<u32>()),
+    ));
+    let err = moves.parse("N3,E4\nE1,W12").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 4));
+    assert_eq!(err.token, "W12");
+    assert_eq!(err.reason, "expected one of N, NE, E");
+
+    let err = moves.parse("N3,Nx").unwrap_err();
+    assert_eq!((err.line, err.column, err.token.as_str()), (1, 5, "x"));
+
+    let err = lines_of(pair(")", word(), word()))
+        .parse("A)B\nB)C)D")
+        .unwrap_err();
+    assert_eq!((err.line, err.column, err.token.as_str()), (2, 3, "C)D"));
+
+    let err = range(from_str::<u32>()).parse("20-10").unwrap_err();
+    assert_eq!(err.reason, "lower bound is above the upper bound");
+}
+
+#[test]
+fn try_map_rejects_values_at_their_token() {
+    let even = from_str::<u32>().try_map(|n| {
+        if n % 2 == 0 {
+            Ok(n)
+        } else {
+            Err("expected an even number".to_string())
+        }
+    });
+    let err = separated_by(",", even).parse("2, 4, 7").unwrap_err();
+    assert_eq!((err.column, err.token.as_str()), (7, "7"));
+}
+
+#[test]
+fn grids_are_rectangular() {
+    let cells = |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    };
+    let parsed = grid(cells).parse("#..\n.#.\n").unwrap();
+    assert_eq!((parsed.width, parsed.height), (3, 2));
+    assert_eq!(parsed.get(1, 1), Some(&true));
+    assert_eq!(parsed.get(3, 0), None);
+    assert_eq!(parsed.rows().count(), 2);
+
+    let err = grid(cells).parse("#..\n.x.").unwrap_err();
+    assert_eq!((err.line, err.column, err.token.as_str()), (2, 2, "x"));
+    let err = grid(cells).parse("#..\n.#").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 1));
+}
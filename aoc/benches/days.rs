@@ -0,0 +1,62 @@
+use aoc::bench::bench_input;
+use aoc::registry;
+use aoc_common::Part;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::path::PathBuf;
+
+/*
+Parse, part 1 and part 2 of every registered day, on the real input when
+dayNN/input.txt exists and on the largest example otherwise. Parsing is
+measured once per distinct input, as `parse2` when part 2 has its own.
+Summarized and compared against a saved baseline by `aoc bench`.
+*/
+
+fn days(c: &mut Criterion) {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..");
+    for entry in registry::days() {
+        let day_dir = root.join(format!("day{:02}", entry.day));
+        let mut group = c.benchmark_group(format!("day{:02}", entry.day));
+        let inputs: Vec<(Part, String)> = Part::BOTH
+            .into_iter()
+            .filter_map(
+                |part| match bench_input(&day_dir, part).expect("readable inputs") {
+                    Some(input) => Some((part, input)),
+                    None => {
+                        eprintln!("day{:02}: no input for part {}", entry.day, part.number());
+                        None
+                    }
+                },
+            )
+            .collect();
+        // Every distinct input is parsed once, whichever parts it serves.
+        let mut parsed: Vec<(&str, Option<_>)> = Vec::new();
+        for (_, input) in &inputs {
+            if parsed.iter().any(|(seen, _)| seen == input) {
+                continue;
+            }
+            let stage = if parsed.is_empty() { "parse" } else { "parse2" };
+            group.bench_function(stage, |b| b.iter(|| (entry.parse)(black_box(input))));
+            let solution = (entry.parse)(input)
+                .map_err(|err| eprintln!("day{:02}: {err:#}", entry.day))
+                .ok();
+            parsed.push((input, solution));
+        }
+        for (part, input) in &inputs {
+            let part = *part;
+            let Some((_, Some(solution))) = parsed.iter().find(|(seen, _)| seen == input) else {
+                continue;
+            };
+            if let Err(err) = (entry.solve)(&**solution, part) {
+                eprintln!("day{:02} part {}: {err:#}", entry.day, part.number());
+                continue;
+            }
+            group.bench_function(format!("part{}", part.number()), |b| {
+                b.iter(|| (entry.solve)(black_box(&**solution), part))
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
@@ -0,0 +1,193 @@
+use anyhow::{Context, Result};
+use aoc_common::{input, normalize_answer, ParseError, Part};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/*
+Known-correct answers of one day, stored in dayNN/answers.txt with one
+answer per line:
+
+    # input part answer
+    input.txt 1 3366415
+    inputs/alice.txt 1 3412207
+
+Inputs are relative to the day directory, so everyone can keep their own
+input (and its answers) next to the others. Newlines in answers (rendered
+text) are stored as `\n` and backslashes as `\\`, trailing whitespace is
+dropped. Spaces in input paths are stored as `\s`.
+*/
+
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+const HEADER: &str = "# input part answer";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recorded {
+    pub input: String,
+    pub part: Part,
+    pub answer: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Answers {
+    path: PathBuf,
+    recorded: Vec<Recorded>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong { expected: String },
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "ok"),
+            Verdict::Wrong { expected } => write!(f, "WRONG, expected {}", escape(expected)),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// How an answer is written to the answers file.
+pub fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+/// How an input is written to the answers file, without spaces so the
+/// fields can be told apart.
+pub fn escape_input(input: &str) -> String {
+    escape(input).replace(' ', "\\s")
+}
+
+/// Reverses `escape` and `escape_input`.
+pub fn unescape(answer: &str) -> String {
+    let mut unescaped = String::with_capacity(answer.len());
+    let mut chars = answer.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                unescaped.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                unescaped.push('\\');
+                chars.next();
+            }
+            ('\\', Some('s')) => {
+                unescaped.push(' ');
+                chars.next();
+            }
+            _ => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+/// One line of the answers file, `line` is part of `text`.
+pub fn parse_line(text: &str, line: &str) -> Result<Recorded, ParseError> {
+    let mut fields = line.splitn(3, ' ');
+    let input = fields.next().unwrap_or_default();
+    let part = fields.next().unwrap_or_default();
+    let part = match part {
+        "1" => Part::One,
+        "2" => Part::Two,
+        _ => {
+            return Err(ParseError::at(
+                text,
+                line,
+                "expected <input> <1|2> <answer>",
+            ))
+        }
+    };
+    let Some(answer) = fields.next() else {
+        return Err(ParseError::at(text, line, "missing answer"));
+    };
+    Ok(Recorded {
+        input: unescape(input),
+        part,
+        answer: unescape(answer),
+    })
+}
+
+impl Answers {
+    /// The answers of the day in `day_dir`, empty if nothing is recorded yet.
+    pub fn load(day_dir: &Path) -> Result<Self> {
+        let path = day_dir.join(ANSWERS_FILE);
+        let mut recorded = Vec::new();
+        if path.exists() {
+            let text = input::read_input(&path.to_string_lossy())?;
+            for line in text.lines() {
+                if line.trim().is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                recorded.push(
+                    parse_line(&text, line).map_err(|err| err.in_file(&path.to_string_lossy()))?,
+                );
+            }
+        }
+        Ok(Answers { path, recorded })
+    }
+
+    pub fn get(&self, input: &str, part: Part) -> Option<&str> {
+        self.recorded
+            .iter()
+            .find(|recorded| recorded.input == input && recorded.part == part)
+            .map(|recorded| recorded.answer.as_str())
+    }
+
+    pub fn set(&mut self, input: &str, part: Part, answer: &str) {
+        match self
+            .recorded
+            .iter_mut()
+            .find(|recorded| recorded.input == input && recorded.part == part)
+        {
+            Some(recorded) => recorded.answer = normalize_answer(answer),
+            None => self.recorded.push(Recorded {
+                input: input.to_string(),
+                part,
+                answer: normalize_answer(answer),
+            }),
+        }
+    }
+
+    /// Every input with at least one recorded answer, in file order.
+    pub fn inputs(&self) -> Vec<&str> {
+        let mut inputs: Vec<&str> = Vec::new();
+        for recorded in &self.recorded {
+            if !inputs.contains(&recorded.input.as_str()) {
+                inputs.push(&recorded.input);
+            }
+        }
+        inputs
+    }
+
+    pub fn verdict(&self, input: &str, part: Part, answer: &str) -> Verdict {
+        match self.get(input, part) {
+            None => Verdict::Unknown,
+            Some(expected) if normalize_answer(expected) == normalize_answer(answer) => {
+                Verdict::Correct
+            }
+            Some(expected) => Verdict::Wrong {
+                expected: expected.to_string(),
+            },
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let mut text = format!("{HEADER}\n");
+        for recorded in &self.recorded {
+            text.push_str(&format!(
+                "{} {} {}\n",
+                escape_input(&recorded.input),
+                recorded.part.number(),
+                escape(&recorded.answer)
+            ));
+        }
+        fs::write(&self.path, text)
+            .with_context(|| format!("Could not write {}", self.path.display()))
+    }
+}
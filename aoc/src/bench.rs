@@ -0,0 +1,111 @@
+use anyhow::{Context, Result};
+use aoc_common::examples::load_examples;
+use aoc_common::{Part, INPUT_FILE};
+use std::fs;
+use std::path::Path;
+
+/*
+Summaries of the Criterion runs of benches/days.rs. Every day is a benchmark
+group `dayNN` with the stages `parse`, `part1` and `part2`, plus `parse2` when
+part 2 is benchmarked on an input of its own. Criterion keeps
+the latest measurement of a stage in
+
+    target/criterion/dayNN/<stage>/new/estimates.json
+
+and saved baselines (`--save-baseline <name>`) in a sibling directory named
+after the baseline, which is what a run gets compared against.
+*/
+
+pub const STAGES: [&str; 4] = ["parse", "parse2", "part1", "part2"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub day: u8,
+    pub stage: String,
+    /// Time per iteration in nanoseconds, as Criterion reports it.
+    pub time: f64,
+    pub baseline: Option<f64>,
+}
+
+impl Comparison {
+    /// Relative change against the baseline in percent, positive is slower.
+    pub fn change(&self) -> Option<f64> {
+        self.baseline
+            .filter(|&baseline| baseline > 0.0)
+            .map(|baseline| (self.time - baseline) / baseline * 100.0)
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change().is_some_and(|change| change > threshold)
+    }
+}
+
+/// The input a day is benchmarked with: the real input when it's on disk,
+/// otherwise the largest example that has an answer for `part`.
+pub fn bench_input(day_dir: &Path, part: Part) -> Result<Option<String>> {
+    let input = day_dir.join(INPUT_FILE);
+    if input.exists() {
+        return fs::read_to_string(&input)
+            .map(Some)
+            .with_context(|| format!("Could not read {}", input.display()));
+    }
+    let examples_dir = day_dir.join("examples");
+    if !examples_dir.exists() {
+        return Ok(None);
+    }
+    Ok(load_examples(&examples_dir)?
+        .into_iter()
+        .filter(|example| example.expected(part).is_some())
+        .map(|example| example.input)
+        .max_by_key(String::len))
+}
+
+/// The slope of the linear regression Criterion prints as the time per
+/// iteration, or the mean for benchmarks too slow to be sampled linearly.
+fn read_time(path: &Path) -> Result<f64> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("Could not read {}", path.display()))?;
+    let estimates: serde_json::Value = serde_json::from_str(&text)
+        .with_context(|| format!("Could not parse {}", path.display()))?;
+    estimates["slope"]["point_estimate"]
+        .as_f64()
+        .or_else(|| estimates["mean"]["point_estimate"].as_f64())
+        .with_context(|| format!("No estimates in {}", path.display()))
+}
+
+/// Latest results of every benchmarked stage next to `baseline`, by day.
+pub fn compare(criterion_dir: &Path, baseline: &str) -> Result<Vec<Comparison>> {
+    let mut comparisons = Vec::new();
+    for day in 1..=25u8 {
+        let day_dir = criterion_dir.join(format!("day{day:02}"));
+        for stage in STAGES {
+            let stage_dir = day_dir.join(stage);
+            let latest = stage_dir.join("new").join("estimates.json");
+            if !latest.exists() {
+                continue;
+            }
+            let saved = stage_dir.join(baseline).join("estimates.json");
+            comparisons.push(Comparison {
+                day,
+                stage: stage.to_string(),
+                time: read_time(&latest)?,
+                baseline: if saved.exists() {
+                    Some(read_time(&saved)?)
+                } else {
+                    None
+                },
+            });
+        }
+    }
+    Ok(comparisons)
+}
+
+/// Nanoseconds the way Criterion prints them.
+pub fn format_nanos(nanos: f64) -> String {
+    match nanos {
+        n if n >= 1e9 => format!("{:.2} s", n / 1e9),
+        n if n >= 1e6 => format!("{:.2} ms", n / 1e6),
+        n if n >= 1e3 => format!("{:.2} µs", n / 1e3),
+        n => format!("{n:.2} ns"),
+    }
+}
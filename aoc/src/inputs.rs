@@ -0,0 +1,89 @@
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/*
+Where puzzle inputs and descriptions come from when a day is scaffolded.
+Nothing here talks to the network: inputs are downloaded once by whatever
+means (browser, aoc-cli, a teammate) into the cache directory
+
+    <cache>/day05/input.txt
+    <cache>/day05/puzzle.md
+
+and `aoc new` copies them from there, so scaffolding works offline and in CI.
+*/
+
+pub const CACHE_ENV: &str = "AOC_CACHE_DIR";
+pub const DEFAULT_CACHE_DIR: &str = ".aoc-cache";
+
+pub trait InputProvider {
+    /// The puzzle input of `day`, `None` if it is not available.
+    fn input(&self, day: u8) -> Result<Option<String>>;
+    /// The puzzle description of `day` as markdown, `None` if it is not
+    /// available.
+    fn puzzle(&self, day: u8) -> Result<Option<String>>;
+}
+
+pub struct CacheDir {
+    dir: PathBuf,
+}
+
+impl CacheDir {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        CacheDir { dir: dir.into() }
+    }
+
+    fn read(&self, day: u8, file: &str) -> Result<Option<String>> {
+        let path = self.dir.join(format!("day{day:02}")).join(file);
+        if !path.exists() {
+            return Ok(None);
+        }
+        fs::read_to_string(&path)
+            .map(Some)
+            .with_context(|| format!("Could not read {}", path.display()))
+    }
+}
+
+impl InputProvider for CacheDir {
+    fn input(&self, day: u8) -> Result<Option<String>> {
+        self.read(day, "input.txt")
+    }
+
+    fn puzzle(&self, day: u8) -> Result<Option<String>> {
+        self.read(day, "puzzle.md")
+    }
+}
+
+/// Fixed inputs, for tests.
+#[derive(Debug, Clone, Default)]
+pub struct Stub {
+    inputs: HashMap<u8, String>,
+    puzzles: HashMap<u8, String>,
+}
+
+impl Stub {
+    pub fn new() -> Self {
+        Stub::default()
+    }
+
+    pub fn with_input(mut self, day: u8, input: &str) -> Self {
+        self.inputs.insert(day, input.to_string());
+        self
+    }
+
+    pub fn with_puzzle(mut self, day: u8, puzzle: &str) -> Self {
+        self.puzzles.insert(day, puzzle.to_string());
+        self
+    }
+}
+
+impl InputProvider for Stub {
+    fn input(&self, day: u8) -> Result<Option<String>> {
+        Ok(self.inputs.get(&day).cloned())
+    }
+
+    fn puzzle(&self, day: u8) -> Result<Option<String>> {
+        Ok(self.puzzles.get(&day).cloned())
+    }
+}
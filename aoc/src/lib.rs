@@ -0,0 +1,13 @@
+/*
+The runner's building blocks, kept out of main.rs so they can be tested and
+shared with the benchmarks: the registry of days, recorded answers, benchmark
+summaries, where puzzle inputs come from, scaffolding of new days and the
+JSON report of a run.
+*/
+
+pub mod answers;
+pub mod bench;
+pub mod inputs;
+pub mod registry;
+pub mod report;
+pub mod scaffold;
@@ -0,0 +1,419 @@
+use anyhow::{bail, Context, Result};
+use aoc::answers::{Answers, Verdict};
+use aoc::bench::{self, Comparison};
+use aoc::inputs::{CacheDir, CACHE_ENV, DEFAULT_CACHE_DIR};
+use aoc::{registry, report, scaffold};
+use aoc_common::{Entry, ParseError, Part, PartResult, Source, INPUT_FILE};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::{Path, PathBuf};
+
+/*
+One runner for every day:
+
+    aoc run 5 --part 2
+    aoc run --all
+    aoc run 3 --input path/to/input.txt
+    aoc run 3 --input alice.txt --input bob.txt
+    aoc run 3 --input -     read the input from stdin
+    aoc run 3 --save        record the answers as correct in day03/answers.txt
+    aoc run --all --verify  compare against the recorded answers
+    aoc run 7 --format json answers and diagnostics as JSON
+    aoc check               re-run every input that has recorded answers
+    aoc new 8               scaffold day08 with its input from the cache
+    aoc bench --save main   benchmark every day and keep it as baseline "main"
+    aoc bench               benchmark again and flag regressions against "main"
+*/
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2019 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// A table, diagnostics below each answer.
+    Text,
+    /// A JSON array with one object per part (see aoc/src/report.rs).
+    Json,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve one day (both parts unless --part is given) or all of them.
+    Run {
+        day: Option<u8>,
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        #[arg(long, conflicts_with = "day")]
+        all: bool,
+        /// Defaults to dayNN/input.txt in the workspace. Repeat it to solve
+        /// several inputs, `-` reads stdin.
+        #[arg(long, conflicts_with = "all")]
+        input: Vec<String>,
+        /// Fail if an answer differs from the one in dayNN/answers.txt.
+        #[arg(long)]
+        verify: bool,
+        /// Store the answers in dayNN/answers.txt as the correct ones.
+        #[arg(long, conflicts_with = "verify")]
+        save: bool,
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Re-run every input with recorded answers (of one day or all days) and
+    /// fail if any answer changed. Inputs that are not on disk are skipped.
+    Check {
+        day: Option<u8>,
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Create the dayNN crate and register it with the runner.
+    New {
+        day: u8,
+        /// Cached inputs (<cache>/dayNN/input.txt and puzzle.md), defaults to
+        /// $AOC_CACHE_DIR or .aoc-cache in the workspace.
+        #[arg(long)]
+        cache: Option<PathBuf>,
+    },
+    /// Run the Criterion benchmarks (benches/days.rs) and compare them with a
+    /// saved baseline.
+    Bench {
+        /// Only benchmark this day.
+        day: Option<u8>,
+        /// Baseline to compare with.
+        #[arg(long, default_value = "main")]
+        baseline: String,
+        /// Save this run as the baseline with the given name instead.
+        #[arg(long, conflicts_with = "baseline")]
+        save: Option<String>,
+        /// Slowdown in percent that counts as a regression.
+        #[arg(long, default_value_t = 5.0)]
+        threshold: f64,
+        /// Summarize the results of the last run without benchmarking.
+        #[arg(long)]
+        no_run: bool,
+    },
+}
+
+fn workspace_root() -> PathBuf {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..");
+    root.canonicalize().unwrap_or(root)
+}
+
+fn day_dir(day: u8) -> PathBuf {
+    workspace_root().join(format!("day{day:02}"))
+}
+
+fn default_input(day: u8) -> PathBuf {
+    day_dir(day).join(INPUT_FILE)
+}
+
+/// How `input` is listed in the answers file: relative to the day directory
+/// when it is inside of it, else as an absolute path, as `check` resolves
+/// it against the day directory.
+fn answers_key(day: u8, input: &Source) -> Result<String> {
+    let Source::File(input) = input else {
+        bail!("Answers of stdin can't be recorded, pass the input as a file");
+    };
+    let input = input
+        .canonicalize()
+        .with_context(|| format!("Could not find {}", input.display()))?;
+    let key = match day_dir(day)
+        .canonicalize()
+        .ok()
+        .and_then(|dir| input.strip_prefix(dir).map(Path::to_path_buf).ok())
+    {
+        Some(relative) => relative,
+        None => input,
+    };
+    Ok(key.to_string_lossy().replace('\\', "/"))
+}
+
+fn solve(entry: Entry, parts: &[Part], input: &Source) -> Result<Vec<PartResult>> {
+    let contents = input.read()?;
+    (entry.run)(&contents, parts).map_err(|err| match err.downcast::<ParseError>() {
+        Ok(err) => err.in_file(&input.to_string()).into(),
+        Err(err) => err.context(format!("Day {} ({input})", entry.day)),
+    })
+}
+
+/// Compares `results` (all of one `input`) against the recorded answers.
+fn verify(results: &[PartResult], input: &str, answers: &Answers) -> Vec<Verdict> {
+    results
+        .iter()
+        .map(|result| answers.verdict(input, result.part, &result.answer))
+        .collect()
+}
+
+fn fail_on_wrong(verdicts: &[Verdict]) -> Result<()> {
+    let wrong = verdicts
+        .iter()
+        .filter(|verdict| matches!(verdict, Verdict::Wrong { .. }))
+        .count();
+    if wrong > 0 {
+        bail!("{wrong} answer(s) differ from the recorded ones");
+    }
+    Ok(())
+}
+
+/// `verdicts` adds a column with the outcome of the check of each result.
+fn print_table(results: &[PartResult], verdicts: Option<&[Verdict]>) {
+    let check_header = if verdicts.is_some() { "  Check" } else { "" };
+    println!(
+        "{:>3}  {:>4}  {:<20}  {:>12}{check_header}",
+        "Day", "Part", "Answer", "Elapsed"
+    );
+    for (index, result) in results.iter().enumerate() {
+        let mut lines = result.answer.lines();
+        let check = verdicts
+            .and_then(|verdicts| verdicts.get(index))
+            .map_or(String::new(), |verdict| format!("  {verdict}"));
+        println!(
+            "{:>3}  {:>4}  {:<20}  {:>12}{check}",
+            result.day,
+            result.part.number(),
+            lines.next().unwrap_or(""),
+            format!("{:.2?}", result.elapsed)
+        );
+        // Multi-line answers (e.g. rendered text) continue below the row.
+        for line in lines {
+            println!("{:>11}{line}", "");
+        }
+        if !result.diagnostics.is_empty() {
+            let diagnostics: Vec<String> = result
+                .diagnostics
+                .iter()
+                .map(|(name, value)| format!("{name}: {value}"))
+                .collect();
+            println!("{:>11}{}", "", diagnostics.join(", "));
+        }
+    }
+}
+
+/// Results grouped by input, each with the verdicts of its check if any.
+type Tables = Vec<(String, Vec<PartResult>, Vec<Verdict>)>;
+
+fn print_json(tables: &Tables, checking: bool) -> Result<()> {
+    let results: Vec<serde_json::Value> = tables
+        .iter()
+        .flat_map(|(input, results, verdicts)| {
+            results.iter().zip(verdicts).map(move |(result, verdict)| {
+                report::result(result, input, checking.then_some(verdict))
+            })
+        })
+        .collect();
+    println!("{}", serde_json::to_string_pretty(&results)?);
+    Ok(())
+}
+
+fn entries(day: Option<u8>) -> Result<Vec<Entry>> {
+    match day {
+        None => Ok(registry::days()),
+        Some(day) => Ok(vec![
+            registry::find(day).with_context(|| format!("Day {day} is not solved"))?
+        ]),
+    }
+}
+
+fn check(day: Option<u8>, format: Format) -> Result<()> {
+    let mut tables: Tables = Vec::new();
+    for entry in entries(day)? {
+        let answers = Answers::load(&day_dir(entry.day))?;
+        for input in answers.inputs() {
+            let path = day_dir(entry.day).join(input);
+            if !path.exists() {
+                eprintln!("Day {}: skipping {input}, not on disk", entry.day);
+                continue;
+            }
+            let parts: Vec<Part> = Part::BOTH
+                .into_iter()
+                .filter(|&part| answers.get(input, part).is_some())
+                .collect();
+            let solved = solve(entry, &parts, &Source::File(path))?;
+            let verdicts = verify(&solved, input, &answers);
+            tables.push((format!("day{:02}/{input}", entry.day), solved, verdicts));
+        }
+    }
+    if tables.is_empty() {
+        bail!("No recorded answers with an input on disk, record some with `aoc run <day> --save`");
+    }
+    match format {
+        Format::Text => {
+            for (input, results, verdicts) in &tables {
+                println!("{input}");
+                print_table(results, Some(verdicts));
+                println!();
+            }
+        }
+        Format::Json => print_json(&tables, true)?,
+    }
+    let verdicts: Vec<Verdict> = tables
+        .into_iter()
+        .flat_map(|(_, _, verdicts)| verdicts)
+        .collect();
+    fail_on_wrong(&verdicts)
+}
+
+fn criterion_dir() -> PathBuf {
+    std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| workspace_root().join("target"))
+        .join("criterion")
+}
+
+fn run_benchmarks(day: Option<u8>, save: Option<&str>) -> Result<()> {
+    let mut cargo = std::process::Command::new(env!("CARGO"));
+    cargo
+        .current_dir(workspace_root())
+        .args(["bench", "-p", "aoc", "--bench", "days", "--"]);
+    if let Some(day) = day {
+        cargo.arg(format!("day{day:02}/"));
+    }
+    if let Some(name) = save {
+        cargo.args(["--save-baseline", name]);
+    }
+    let status = cargo.status().context("Could not run cargo bench")?;
+    if !status.success() {
+        bail!("cargo bench failed ({status})");
+    }
+    Ok(())
+}
+
+fn print_bench_table(comparisons: &[Comparison], threshold: f64) {
+    println!(
+        "{:>3}  {:<5}  {:>12}  {:>12}  {:>8}",
+        "Day", "Stage", "Time", "Baseline", "Change"
+    );
+    for comparison in comparisons {
+        let flag = if comparison.is_regression(threshold) {
+            "  REGRESSION"
+        } else {
+            ""
+        };
+        println!(
+            "{:>3}  {:<5}  {:>12}  {:>12}  {:>8}{flag}",
+            comparison.day,
+            comparison.stage,
+            bench::format_nanos(comparison.time),
+            comparison
+                .baseline
+                .map_or("-".to_string(), bench::format_nanos),
+            comparison
+                .change()
+                .map_or("-".to_string(), |change| format!("{change:+.1}%")),
+        );
+    }
+}
+
+fn main() -> Result<()> {
+    match Cli::parse().command {
+        Command::Run {
+            day,
+            part,
+            all,
+            input,
+            verify: verifying,
+            save,
+            format,
+        } => {
+            let parts = match part {
+                Some(1) => vec![Part::One],
+                Some(_) => vec![Part::Two],
+                None => Part::BOTH.to_vec(),
+            };
+            if day.is_none() && !all {
+                bail!("Pass a day or --all");
+            }
+
+            let mut tables: Tables = Vec::new();
+            for entry in entries(day)? {
+                let sources: Vec<Source> = if input.is_empty() {
+                    vec![Source::File(default_input(entry.day))]
+                } else {
+                    input.iter().map(|arg| Source::from_arg(arg)).collect()
+                };
+                let mut answers = Answers::load(&day_dir(entry.day))?;
+                for source in &sources {
+                    let solved = solve(entry, &parts, source)?;
+                    let key = match source {
+                        Source::Stdin if !save => source.to_string(),
+                        _ => answers_key(entry.day, source)?,
+                    };
+                    if save {
+                        for result in &solved {
+                            answers.set(&key, result.part, &result.answer);
+                        }
+                        answers.save()?;
+                    }
+                    let verdicts = verify(&solved, &key, &answers);
+                    tables.push((source.to_string(), solved, verdicts));
+                }
+            }
+
+            let checking = verifying || save;
+            match format {
+                // One table for a single input per day, one per input otherwise.
+                Format::Text if input.len() <= 1 => {
+                    let (results, verdicts): (Vec<PartResult>, Vec<Verdict>) = tables
+                        .iter()
+                        .flat_map(|(_, results, verdicts)| {
+                            results.iter().cloned().zip(verdicts.iter().cloned())
+                        })
+                        .unzip();
+                    print_table(&results, checking.then_some(&verdicts[..]));
+                }
+                Format::Text => {
+                    for (input, results, verdicts) in &tables {
+                        println!("{input}");
+                        print_table(results, checking.then_some(&verdicts[..]));
+                    }
+                }
+                Format::Json => print_json(&tables, checking)?,
+            }
+            if verifying {
+                let verdicts: Vec<Verdict> = tables
+                    .into_iter()
+                    .flat_map(|(_, _, verdicts)| verdicts)
+                    .collect();
+                fail_on_wrong(&verdicts)?;
+            }
+        }
+        Command::Check { day, format } => check(day, format)?,
+        Command::New { day, cache } => {
+            let cache = cache
+                .or_else(|| std::env::var_os(CACHE_ENV).map(PathBuf::from))
+                .unwrap_or_else(|| workspace_root().join(DEFAULT_CACHE_DIR));
+            let dir = scaffold::new_day(&workspace_root(), day, &CacheDir::new(cache))?;
+            println!("Created {}", dir.display());
+        }
+        Command::Bench {
+            day,
+            baseline,
+            save,
+            threshold,
+            no_run,
+        } => {
+            if !no_run {
+                run_benchmarks(day, save.as_deref())?;
+            }
+            let baseline = save.unwrap_or(baseline);
+            let comparisons: Vec<Comparison> = bench::compare(&criterion_dir(), &baseline)?
+                .into_iter()
+                .filter(|comparison| day.is_none_or(|day| comparison.day == day))
+                .collect();
+            if comparisons.is_empty() {
+                bail!("No benchmark results in {}", criterion_dir().display());
+            }
+            print_bench_table(&comparisons, threshold);
+            let regressions = comparisons
+                .iter()
+                .filter(|comparison| comparison.is_regression(threshold))
+                .count();
+            if regressions > 0 {
+                bail!("{regressions} stage(s) got more than {threshold}% slower than {baseline:?}");
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use aoc_common::Entry;
+
+/// Every solved day, in order. New days are added here.
+pub fn days() -> Vec<Entry> {
+    vec![
+        Entry::of::<day01::Day01>(),
+        Entry::of::<day02::Day02>(),
+        Entry::of::<day03::Day03>(),
+        Entry::of::<day04::Day04>(),
+        Entry::of::<day05::Day05>(),
+        Entry::of::<day06::Day06>(),
+        Entry::of::<day07::Day07>(),
+        Entry::of::<day11::Day11>(),
+        Entry::of::<day13::Day13>(),
+    ]
+}
+
+pub fn find(day: u8) -> Option<Entry> {
+    days().into_iter().find(|entry| entry.day == day)
+}
@@ -0,0 +1,67 @@
+use crate::answers::Verdict;
+use aoc_common::{PartResult, Value};
+use serde_json::{json, Map, Number};
+
+/*
+`--format json`: one object per solved part, for scripts and dashboards.
+
+    {"day":7,"part":1,"input":"day07/input.txt","answer":43210,
+     "elapsed_ns":81234,"diagnostics":{"phases":[4,3,2,1,0]},"check":"ok"}
+
+Integer answers are JSON numbers however big they are (serde_json keeps
+their digits), everything else (rendered text) a string.
+`check` is only there when the answers were verified, `expected` only when
+the answer is wrong.
+*/
+
+fn to_json(value: &Value) -> serde_json::Value {
+    match value {
+        Value::Number(number) => json!(number),
+        Value::Numbers(numbers) => json!(numbers),
+        Value::Text(text) => json!(text),
+    }
+}
+
+/// Whether `answer` is an integer written the way JSON writes it, so e.g.
+/// `007` stays the text it is.
+fn is_integer(answer: &str) -> bool {
+    let digits = answer.strip_prefix('-').unwrap_or(answer);
+    !digits.is_empty()
+        && digits.bytes().all(|byte| byte.is_ascii_digit())
+        && (digits == "0" || !digits.starts_with('0'))
+}
+
+fn answer_to_json(answer: &str) -> serde_json::Value {
+    if is_integer(answer) {
+        if let Ok(number) = serde_json::from_str::<Number>(answer) {
+            return serde_json::Value::Number(number);
+        }
+    }
+    json!(answer)
+}
+
+pub fn result(result: &PartResult, input: &str, verdict: Option<&Verdict>) -> serde_json::Value {
+    let diagnostics: Map<String, serde_json::Value> = result
+        .diagnostics
+        .iter()
+        .map(|(name, value)| (name.to_string(), to_json(value)))
+        .collect();
+    let mut object = json!({
+        "day": result.day,
+        "part": result.part.number(),
+        "input": input,
+        "answer": answer_to_json(&result.answer),
+        "elapsed_ns": result.elapsed.as_nanos() as u64,
+        "diagnostics": diagnostics,
+    });
+    match verdict {
+        Some(Verdict::Correct) => object["check"] = json!("ok"),
+        Some(Verdict::Wrong { expected }) => {
+            object["check"] = json!("wrong");
+            object["expected"] = answer_to_json(expected);
+        }
+        Some(Verdict::Unknown) => object["check"] = json!("unknown"),
+        None => {}
+    }
+    object
+}
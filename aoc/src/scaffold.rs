@@ -0,0 +1,173 @@
+use crate::inputs::InputProvider;
+use anyhow::{bail, Context, Result};
+use aoc_common::INPUT_FILE;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/*
+`aoc new <day>`: creates the dayNN crate from the templates in aoc/templates
+and registers it in the workspace members, as a dependency of the runner and
+in its registry of days. Input and puzzle description are copied from an
+`InputProvider` when it has them.
+
+Whatever can fail without writing (the provider, finding where the day goes
+in the registrations) is done first. If writing fails after all, the new
+crate is removed and the registrations are put back, so a failed `aoc new`
+can simply be run again.
+*/
+
+pub const YEAR: u16 = 2019;
+
+const CARGO_TOML: &str = include_str!("../templates/Cargo.toml.tmpl");
+const LIB_RS: &str = include_str!("../templates/lib.rs.tmpl");
+const PART_RS: &str = include_str!("../templates/part.rs.tmpl");
+const EXAMPLES_RS: &str = include_str!("../templates/examples.rs.tmpl");
+const EXAMPLE_TXT: &str = include_str!("../templates/example.txt.tmpl");
+
+/// Fills in the `{{name}}` placeholders of `template`.
+fn render(template: &str, day: u8, part: u8) -> String {
+    template
+        .replace("{{crate}}", &format!("day{day:02}"))
+        .replace("{{solution}}", &format!("Day{day:02}"))
+        .replace("{{year}}", &YEAR.to_string())
+        .replace("{{nn}}", &format!("{day:02}"))
+        .replace("{{day}}", &day.to_string())
+        .replace("{{part}}", &part.to_string())
+}
+
+/// Inserts `line` into the run of lines matching `is_day_line`, keeping them
+/// sorted. Days are zero-padded, so text order is day order.
+fn insert_sorted(text: &str, is_day_line: impl Fn(&str) -> bool, line: &str) -> Result<String> {
+    let lines: Vec<&str> = text.lines().collect();
+    if lines.contains(&line) {
+        return Ok(text.to_string());
+    }
+    let days: Vec<usize> = (0..lines.len())
+        .filter(|&index| is_day_line(lines[index]))
+        .collect();
+    let (Some(&first), Some(&last)) = (days.first(), days.last()) else {
+        bail!("No day is registered yet, can't tell where {line:?} goes");
+    };
+    let at = days
+        .iter()
+        .copied()
+        .find(|&index| lines[index] > line)
+        .unwrap_or(last + 1)
+        .max(first);
+
+    let mut updated: Vec<&str> = lines[..at].to_vec();
+    updated.push(line);
+    updated.extend_from_slice(&lines[at..]);
+    Ok(updated.join("\n") + "\n")
+}
+
+/// A file the new day is added to, before and after.
+struct Registration {
+    path: PathBuf,
+    original: String,
+    updated: String,
+}
+
+impl Registration {
+    fn new(path: PathBuf, is_day_line: impl Fn(&str) -> bool, line: &str) -> Result<Self> {
+        let original = fs::read_to_string(&path)
+            .with_context(|| format!("Could not read {}", path.display()))?;
+        let updated = insert_sorted(&original, is_day_line, line)?;
+        Ok(Registration {
+            path,
+            original,
+            updated,
+        })
+    }
+}
+
+/// Writes all `registrations`, or restores the ones already written.
+fn register(registrations: &[Registration]) -> Result<()> {
+    for (done, registration) in registrations.iter().enumerate() {
+        if let Err(err) = fs::write(&registration.path, &registration.updated) {
+            for earlier in &registrations[..done] {
+                let _ = fs::write(&earlier.path, &earlier.original);
+            }
+            return Err(err)
+                .with_context(|| format!("Could not write {}", registration.path.display()));
+        }
+    }
+    Ok(())
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Could not create {}", parent.display()))?;
+    }
+    fs::write(path, contents).with_context(|| format!("Could not write {}", path.display()))
+}
+
+/// Scaffolds `day` in the workspace at `root` and returns the new crate's
+/// directory.
+pub fn new_day(root: &Path, day: u8, provider: &dyn InputProvider) -> Result<PathBuf> {
+    if !(1..=25).contains(&day) {
+        bail!("There is no day {day}, days go from 1 to 25");
+    }
+    let name = format!("day{day:02}");
+    let dir = root.join(&name);
+    if dir.exists() {
+        bail!("{} already exists", dir.display());
+    }
+
+    let input = provider.input(day)?;
+    let puzzle = provider.puzzle(day)?;
+    let registrations = [
+        Registration::new(
+            root.join("Cargo.toml"),
+            |line| line.starts_with("    \"day"),
+            &format!("    \"{name}\","),
+        )?,
+        Registration::new(
+            root.join("aoc/Cargo.toml"),
+            |line| line.starts_with("day"),
+            &format!("{name} = {{ path = \"../{name}\" }}"),
+        )?,
+        Registration::new(
+            root.join("aoc/src/registry.rs"),
+            |line| line.trim_start().starts_with("Entry::of::<day"),
+            &format!("        Entry::of::<{name}::Day{day:02}>(),"),
+        )?,
+    ];
+
+    let created = write_crate(&dir, day, input.as_deref(), puzzle.as_deref())
+        .and_then(|()| register(&registrations));
+    if let Err(err) = created {
+        let _ = fs::remove_dir_all(&dir);
+        return Err(err);
+    }
+    if input.is_none() {
+        eprintln!("No input cached for day {day}, add {name}/{INPUT_FILE} yourself");
+    }
+    Ok(dir)
+}
+
+/// The files of the new crate in `dir`.
+fn write_crate(dir: &Path, day: u8, input: Option<&str>, puzzle: Option<&str>) -> Result<()> {
+    write(&dir.join("Cargo.toml"), &render(CARGO_TOML, day, 0))?;
+    write(&dir.join("src/lib.rs"), &render(LIB_RS, day, 0))?;
+    for part in 1..=2 {
+        write(
+            &dir.join(format!("src/bin/part{part}.rs")),
+            &render(PART_RS, day, part),
+        )?;
+    }
+    write(&dir.join("tests/examples.rs"), &render(EXAMPLES_RS, day, 0))?;
+    write(
+        &dir.join("examples/example.txt"),
+        &render(EXAMPLE_TXT, day, 0),
+    )?;
+
+    if let Some(input) = input {
+        write(&dir.join(INPUT_FILE), input)?;
+    }
+    if let Some(puzzle) = puzzle {
+        write(&dir.join("puzzle.md"), puzzle)?;
+    }
+    Ok(())
+}
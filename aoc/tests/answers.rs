@@ -0,0 +1,102 @@
+use aoc::answers::{
+    escape, escape_input, parse_line, unescape, Answers, Recorded, Verdict, ANSWERS_FILE,
+};
+use aoc_common::Part;
+use std::fs;
+use std::path::PathBuf;
+
+fn day_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc-answers-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn escaping_round_trips() {
+    for text in [
+        "3366415",
+        "#..#\n#..#",
+        "a\\nb",
+        "trailing \\",
+        "two  spaces",
+    ] {
+        assert_eq!(unescape(&escape(text)), text);
+        assert_eq!(unescape(&escape_input(text)), text);
+        assert!(!escape(text).contains('\n'));
+        assert!(!escape_input(text).contains(' '));
+    }
+    assert_eq!(escape("#.\n.#"), "#.\\n.#");
+    assert_eq!(escape_input("my inputs\\a.txt"), "my\\sinputs\\\\a.txt");
+}
+
+#[test]
+fn lines_are_input_part_and_answer() {
+    let text = "inputs/alice\\sb.txt 2 #.\\n.#\nday03 3 12\ninput.txt 1\n";
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(
+        parse_line(text, lines[0]).unwrap(),
+        Recorded {
+            input: "inputs/alice b.txt".to_string(),
+            part: Part::Two,
+            answer: "#.\n.#".to_string(),
+        }
+    );
+    let err = parse_line(text, lines[1]).unwrap_err();
+    assert_eq!(
+        (err.line, err.reason.as_str()),
+        (2, "expected <input> <1|2> <answer>")
+    );
+    let err = parse_line(text, lines[2]).unwrap_err();
+    assert_eq!((err.line, err.reason.as_str()), (3, "missing answer"));
+}
+
+#[test]
+fn saved_answers_load_again() {
+    let dir = day_dir("save");
+    let mut answers = Answers::load(&dir).unwrap();
+    assert_eq!(answers.inputs(), Vec::<&str>::new());
+    answers.set("input.txt", Part::One, "3366415");
+    answers.set("my inputs/bob.txt", Part::Two, "#..#\n#..#  \n");
+    answers.set("input.txt", Part::Two, "5046772");
+    answers.set("input.txt", Part::One, "3366416");
+    answers.save().unwrap();
+
+    let text = fs::read_to_string(dir.join(ANSWERS_FILE)).unwrap();
+    assert_eq!(
+        text,
+        "# input part answer\ninput.txt 1 3366416\nmy\\sinputs/bob.txt 2 #..#\\n#..#\ninput.txt 2 5046772\n"
+    );
+    let loaded = Answers::load(&dir).unwrap();
+    assert_eq!(loaded.inputs(), ["input.txt", "my inputs/bob.txt"]);
+    assert_eq!(
+        loaded.get("my inputs/bob.txt", Part::Two),
+        Some("#..#\n#..#")
+    );
+    assert_eq!(loaded.get("input.txt", Part::One), Some("3366416"));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn verdicts_compare_normalized_answers() {
+    let dir = day_dir("verdict");
+    let mut answers = Answers::load(&dir).unwrap();
+    answers.set("input.txt", Part::One, "#.\n.#");
+    assert_eq!(
+        answers.verdict("input.txt", Part::One, "#.  \n.#\n"),
+        Verdict::Correct
+    );
+    let wrong = answers.verdict("input.txt", Part::One, "..\n..");
+    assert_eq!(
+        wrong,
+        Verdict::Wrong {
+            expected: "#.\n.#".to_string()
+        }
+    );
+    assert_eq!(wrong.to_string(), "WRONG, expected #.\\n.#");
+    assert_eq!(
+        answers.verdict("input.txt", Part::Two, "1"),
+        Verdict::Unknown
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
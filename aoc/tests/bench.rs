@@ -0,0 +1,40 @@
+use aoc::bench::compare;
+use std::fs;
+use std::path::Path;
+
+fn estimates(dir: &Path, slope: f64) {
+    fs::create_dir_all(dir).unwrap();
+    fs::write(
+        dir.join("estimates.json"),
+        format!(
+            r#"{{"mean":{{"point_estimate":{}}},"slope":{{"point_estimate":{slope}}}}}"#,
+            slope * 1.1
+        ),
+    )
+    .unwrap();
+}
+
+#[test]
+fn compare_flags_stages_slower_than_the_baseline() {
+    let criterion = std::env::temp_dir().join(format!("aoc-bench-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&criterion);
+    estimates(&criterion.join("day03/part1/new"), 120.0);
+    estimates(&criterion.join("day03/part1/main"), 100.0);
+    estimates(&criterion.join("day03/part2/new"), 100.0);
+    estimates(&criterion.join("day03/part2/main"), 104.0);
+    estimates(&criterion.join("day04/parse/new"), 50.0);
+
+    let comparisons = compare(&criterion, "main").unwrap();
+    fs::remove_dir_all(&criterion).unwrap();
+
+    let stages: Vec<(u8, &str)> = comparisons
+        .iter()
+        .map(|comparison| (comparison.day, comparison.stage.as_str()))
+        .collect();
+    assert_eq!(stages, vec![(3, "part1"), (3, "part2"), (4, "parse")]);
+    assert!(comparisons[0].is_regression(5.0));
+    assert!(!comparisons[0].is_regression(25.0));
+    assert!(!comparisons[1].is_regression(5.0));
+    assert_eq!(comparisons[2].baseline, None);
+    assert!(!comparisons[2].is_regression(0.0));
+}
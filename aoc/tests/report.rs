@@ -0,0 +1,70 @@
+use aoc::answers::Verdict;
+use aoc::report;
+use aoc_common::{Part, PartResult, Value};
+use serde_json::json;
+use std::time::Duration;
+
+fn phases() -> PartResult {
+    PartResult {
+        day: 7,
+        part: Part::One,
+        answer: "43210".to_string(),
+        diagnostics: vec![("phases", Value::Numbers(vec![4, 3, 2, 1, 0]))],
+        elapsed: Duration::from_micros(5),
+    }
+}
+
+#[test]
+fn results_carry_the_answer_and_diagnostics_as_json() {
+    assert_eq!(
+        report::result(&phases(), "day07/input.txt", None),
+        json!({
+            "day": 7,
+            "part": 1,
+            "input": "day07/input.txt",
+            "answer": 43210,
+            "elapsed_ns": 5000,
+            "diagnostics": {"phases": [4, 3, 2, 1, 0]},
+        })
+    );
+}
+
+#[test]
+fn checked_results_report_what_was_expected() {
+    let wrong = Verdict::Wrong {
+        expected: "54321".to_string(),
+    };
+    let json = report::result(&phases(), "day07/input.txt", Some(&wrong));
+    assert_eq!(json["check"], "wrong");
+    assert_eq!(json["expected"], 54321);
+
+    let mut text = phases();
+    text.answer = "#..#\n####".to_string();
+    let json = report::result(&text, "-", Some(&Verdict::Unknown));
+    assert_eq!(json["answer"], "#..#\n####");
+    assert_eq!(json["check"], "unknown");
+}
+
+#[test]
+fn answers_are_numbers_whatever_their_size() {
+    let answer = |answer: &str| {
+        let mut result = phases();
+        result.answer = answer.to_string();
+        report::result(&result, "-", None)["answer"].clone()
+    };
+    // More than u64 and even u128 hold, as day01 sums with --width big.
+    for big in [
+        "18446744073709551616",
+        "-9223372036854775809",
+        "1000000000000000000000000000000000000000000",
+    ] {
+        let json = answer(big);
+        assert!(json.is_number(), "{big}");
+        assert_eq!(serde_json::to_string(&json).unwrap(), big);
+    }
+    assert_eq!(answer("0"), json!(0));
+    assert_eq!(answer("-12"), json!(-12));
+    for text in ["007", "-", "1.5", "1e5", " 12", ""] {
+        assert_eq!(answer(text), json!(text), "{text:?}");
+    }
+}
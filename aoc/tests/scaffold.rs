@@ -0,0 +1,109 @@
+use aoc::inputs::Stub;
+use aoc::scaffold::new_day;
+use std::fs;
+use std::path::PathBuf;
+
+const WORKSPACE: &str = r#"[workspace]
+members = [
+    "aoc",
+    "day01",
+    "day03",
+]
+"#;
+
+const RUNNER: &str = r#"[dependencies]
+anyhow = "1.0.93"
+day01 = { path = "../day01" }
+day03 = { path = "../day03" }
+"#;
+
+const REGISTRY: &str = "pub fn days() -> Vec<Entry> {
+    vec![
+        Entry::of::<day01::Day01>(),
+        Entry::of::<day03::Day03>(),
+    ]
+}
+";
+
+fn workspace(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("aoc-scaffold-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("aoc/src")).unwrap();
+    fs::write(root.join("Cargo.toml"), WORKSPACE).unwrap();
+    fs::write(root.join("aoc/Cargo.toml"), RUNNER).unwrap();
+    fs::write(root.join("aoc/src/registry.rs"), REGISTRY).unwrap();
+    root
+}
+
+#[test]
+fn new_day_creates_the_crate_and_registers_it_in_order() {
+    let root = workspace("register");
+    let provider = Stub::new()
+        .with_input(2, "1,0,0,0,99\n")
+        .with_puzzle(2, "# Day 2\n");
+
+    let dir = new_day(&root, 2, &provider).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(dir.join("input.txt")).unwrap(),
+        "1,0,0,0,99\n"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("puzzle.md")).unwrap(),
+        "# Day 2\n"
+    );
+    let lib = fs::read_to_string(dir.join("src/lib.rs")).unwrap();
+    assert!(lib.contains("pub struct Day02;"));
+    assert!(lib.contains("const DAY: u8 = 2;"));
+    // The example answers aren't filled in yet, the tests wait for them.
+    let examples = fs::read_to_string(dir.join("tests/examples.rs")).unwrap();
+    assert!(examples.contains("example_tests!(day02::Day02, pending);"));
+    let part2 = fs::read_to_string(dir.join("src/bin/part2.rs")).unwrap();
+    assert!(part2.contains("Day02::part2(input)"));
+    assert!(fs::read_to_string(dir.join("Cargo.toml"))
+        .unwrap()
+        .contains("name = \"day02-part1\""));
+
+    let workspace = fs::read_to_string(root.join("Cargo.toml")).unwrap();
+    assert!(workspace.contains("    \"day01\",\n    \"day02\",\n    \"day03\",\n"));
+    let runner = fs::read_to_string(root.join("aoc/Cargo.toml")).unwrap();
+    assert!(runner.contains("day01 = { path = \"../day01\" }\nday02 = { path = \"../day02\" }\n"));
+    let registry = fs::read_to_string(root.join("aoc/src/registry.rs")).unwrap();
+    assert!(registry.contains("Entry::of::<day02::Day02>(),\n        Entry::of::<day03::Day03>(),"));
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn new_day_works_without_cached_input_but_never_overwrites() {
+    let root = workspace("offline");
+
+    let dir = new_day(&root, 25, &Stub::new()).unwrap();
+    assert!(!dir.join("input.txt").exists());
+    let registry = fs::read_to_string(root.join("aoc/src/registry.rs")).unwrap();
+    assert!(registry.contains("Entry::of::<day03::Day03>(),\n        Entry::of::<day25::Day25>(),"));
+
+    assert!(new_day(&root, 25, &Stub::new()).is_err());
+    assert!(new_day(&root, 26, &Stub::new()).is_err());
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn a_failed_registration_leaves_nothing_behind() {
+    let root = workspace("failed");
+    fs::write(root.join("aoc/src/registry.rs"), "pub fn days() {}\n").unwrap();
+
+    assert!(new_day(&root, 4, &Stub::new().with_input(4, "1\n")).is_err());
+    assert!(!root.join("day04").exists());
+    assert_eq!(
+        fs::read_to_string(root.join("Cargo.toml")).unwrap(),
+        WORKSPACE
+    );
+    assert_eq!(
+        fs::read_to_string(root.join("aoc/Cargo.toml")).unwrap(),
+        RUNNER
+    );
+
+    fs::remove_dir_all(&root).unwrap();
+}
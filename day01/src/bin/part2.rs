@@ -1,50 +1,24 @@
 use anyhow::Result;
-use std::fs::File;
-use std::io::Read;
-
-#[allow(dead_code)]
-const INPUT_FILE: &str = "input.txt";
-#[allow(dead_code)]
-const EXAMPLE_FILE: &str = "example.txt";
+use day01::{cli, FuelModel};
 
 /*
 https://adventofcode.com/2019/day/01
-*/
-
-pub fn read_input(file_path: &str) -> Result<String> {
-    let mut file = File::open(file_path).expect("File not found");
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)
-        .expect("Error reading file");
-    Ok(contents)
-}
 
-fn calculate_fuel(mass: i32) -> i32 {
-    let needed_fuel = mass / 3 - 2;
-    if needed_fuel < 0 {
-        0
-    } else {
-        needed_fuel
-    }
-}
-
-fn calculate_fuel_recursive(mass: i32) -> i32 {
-    let fuel = calculate_fuel(mass);
-    if fuel <= 0 {
-        0
-    } else {
-        fuel + calculate_fuel_recursive(fuel)
-    }
-}
+Usage: day01-part2 [<input>... | -] [--width u64|u128|big] [--model <file>]
+       [--report text|csv|json]
+       day01-part2 --budget <fuel> [--modules <count>] [--model <file>]
 
-fn main() {
-    #[allow(unused_variables)]
-    let contents: String = read_input(INPUT_FILE).unwrap_or_else(|err| panic!("{}", err));
+The masses are streamed, inputs of any size work. Sums that overflow u64 need
+a wider type, `big` needs the `bigint` feature. `--model` swaps the rocket
+equation for the one in <file> (see src/model.rs). `--report` lists the fuel
+of every module with statistics instead of the total. `--budget` answers the
+opposite question: the heaviest module (or <count> equal modules) the fuel is
+enough for.
+*/
 
-    let total_fuel_needed = contents.lines().map(|line| {
-        let mass: i32 = line.parse().expect("Couldnt parse str to int");
-        calculate_fuel_recursive(mass)
-    }).sum::<i32>();
+const USAGE: &str = "Usage: day01-part2 [<input>... | -] [--width u64|u128|big] [--model <file>] \
+                     [--report text|csv|json] [--budget <fuel> [--modules <count>]]";
 
-    println!("Total fuel needed recursive: {}", total_fuel_needed);
+fn main() -> Result<()> {
+    cli::run(FuelModel::PART2, "Total fuel needed recursive", USAGE)
 }
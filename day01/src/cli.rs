@@ -0,0 +1,178 @@
+use crate::fuel::total_fuel_streamed;
+use crate::inverse::{masses_needing, max_masses};
+use crate::model::FuelModel;
+use crate::report::{stream_report, ReportFormat};
+use anyhow::{bail, Result};
+use aoc_common::input::in_file;
+use aoc_common::{default_input, for_each_source, sources, Source};
+use std::path::Path;
+use std::str::FromStr;
+
+/*
+The day01 binaries: both stream their inputs and sum the fuel (or write
+the report) in the requested width, they only differ in the model they
+start from. A budget turns the question around and reads no input at all.
+*/
+
+/// The integer type the fuel is summed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Width {
+    U64,
+    U128,
+    #[cfg(feature = "bigint")]
+    Big,
+}
+
+impl FromStr for Width {
+    type Err = anyhow::Error;
+
+    fn from_str(width: &str) -> Result<Self> {
+        match width {
+            "u64" => Ok(Width::U64),
+            "u128" => Ok(Width::U128),
+            #[cfg(feature = "bigint")]
+            "big" => Ok(Width::Big),
+            #[cfg(not(feature = "bigint"))]
+            "big" => bail!("Build with --features bigint for arbitrary widths"),
+            _ => bail!("Unknown width {width:?}, expected u64, u128 or big"),
+        }
+    }
+}
+
+/// Streams `source` and sums its fuel in `width`, for the day binaries.
+pub fn stream_total(source: &Source, width: Width, model: &FuelModel) -> Result<String> {
+    let reader = source.open()?;
+    let total = match width {
+        Width::U64 => total_fuel_streamed::<u64>(reader, model).map(|total| total.to_string()),
+        Width::U128 => total_fuel_streamed::<u128>(reader, model).map(|total| total.to_string()),
+        #[cfg(feature = "bigint")]
+        Width::Big => {
+            total_fuel_streamed::<num_bigint::BigUint>(reader, model).map(|total| total.to_string())
+        }
+    };
+    total.map_err(|err| in_file(err, &source.to_string()))
+}
+
+/// Streams the report on `source` in `format` to stdout, summed in `width`.
+fn print_report(
+    source: &Source,
+    width: Width,
+    format: ReportFormat,
+    model: &FuelModel,
+) -> Result<()> {
+    let reader = source.open()?;
+    let out = std::io::stdout().lock();
+    let report = match width {
+        Width::U64 => stream_report::<u64>(reader, model, format, out),
+        Width::U128 => stream_report::<u128>(reader, model, format, out),
+        #[cfg(feature = "bigint")]
+        Width::Big => stream_report::<num_bigint::BigUint>(reader, model, format, out),
+    };
+    report.map_err(|err| in_file(err, &source.to_string()))
+}
+
+/// What a day binary was asked to do.
+pub struct Options {
+    pub width: Width,
+    pub model: FuelModel,
+    pub report: Option<ReportFormat>,
+    /// Fuel to find the heaviest modules for.
+    pub budget: Option<u64>,
+    /// How many modules share the budget.
+    pub modules: u64,
+    pub paths: Vec<String>,
+}
+
+/// Parses `--width <u64|u128|big>`, `--model <file>` (on top of `model`),
+/// `--report <text|csv|json>`, `--budget <fuel>`, `--modules <count>` and
+/// the input paths of a day binary.
+pub fn parse_args(
+    mut args: impl Iterator<Item = String>,
+    model: FuelModel,
+    usage: &str,
+) -> Result<Options> {
+    let mut options = Options {
+        width: Width::U64,
+        model,
+        report: None,
+        budget: None,
+        modules: 1,
+        paths: Vec::new(),
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--width" | "--model" | "--report" | "--budget" | "--modules" => {
+                let Some(value) = args.next() else {
+                    bail!("{usage}");
+                };
+                match arg.as_str() {
+                    "--width" => options.width = value.parse()?,
+                    "--model" => options.model = FuelModel::load(Path::new(&value), model)?,
+                    "--report" => options.report = Some(value.parse()?),
+                    "--budget" => options.budget = Some(value.parse()?),
+                    _ => {
+                        options.modules = value.parse()?;
+                        if options.modules == 0 {
+                            bail!("--modules needs at least one module");
+                        }
+                    }
+                }
+            }
+            _ => options.paths.push(arg),
+        }
+    }
+    Ok(options)
+}
+
+/// Runs a day binary that starts from `model` and prints its totals after
+/// `label`.
+pub fn run(model: FuelModel, label: &str, usage: &str) -> Result<()> {
+    let options = parse_args(std::env::args().skip(1), model, usage)?;
+    if let Some(budget) = options.budget {
+        return print_limits(budget, options.modules, &options.model);
+    }
+    let inputs = sources(options.paths, default_input!());
+    for_each_source(&inputs, |_, source| {
+        match options.report {
+            Some(format) => print_report(source, options.width, format, &options.model)?,
+            None => println!(
+                "{label}: {}",
+                stream_total(source, options.width, &options.model)?
+            ),
+        }
+        Ok(())
+    })
+}
+
+/// The heaviest modules `budget` fuels, and the masses that use it exactly.
+fn print_limits(budget: u64, modules: u64, model: &FuelModel) -> Result<()> {
+    let Some(limit) = max_masses(budget, modules, model)? else {
+        println!("No module fits within {budget} fuel");
+        return Ok(());
+    };
+    let next = match limit.next_fuel {
+        Some(next) => format!("{} needs {next}", limit.mass + 1),
+        None => "no u64 is heavier".to_string(),
+    };
+    let proof = format!("{} needs {}, {next}", limit.mass, limit.fuel);
+    if modules > 1 {
+        println!(
+            "Heaviest {modules} modules within {budget} fuel: {} each ({proof})",
+            limit.mass
+        );
+        return Ok(());
+    }
+    println!(
+        "Heaviest module within {budget} fuel: {} ({proof})",
+        limit.mass
+    );
+    match masses_needing(budget, model)? {
+        Some(masses) => println!(
+            "Masses needing exactly {budget} fuel: {} to {}",
+            masses.start(),
+            masses.end()
+        ),
+        None => println!("No mass needs exactly {budget} fuel"),
+    }
+    Ok(())
+}
@@ -0,0 +1,161 @@
+use crate::model::FuelModel;
+use anyhow::{anyhow, Result};
+use aoc_common::ParseError;
+use std::fmt::Display;
+use std::io::BufRead;
+use std::str::FromStr;
+
+/*
+The rocket equation for any unsigned integer width: a mass needs
+`mass / 3 - 2` fuel, clamped to zero (see `FuelModel` for other engines).
+Fuel is always less than the mass it lifts (recursive fuel less than half of
+it), so only the total over many modules can overflow, and that sum is
+checked.
+
+`u32`, `u64` and `u128` work out of the box, the `bigint` feature adds
+`num_bigint::BigUint`, which never overflows.
+*/
+
+pub trait Quantity: Clone + Ord + Display + FromStr<Err: Display> {
+    fn from_u32(n: u32) -> Self;
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+    /// Rounds down, `divisor` is not zero.
+    fn div(&self, divisor: &Self) -> Self;
+    fn rem(&self, divisor: &Self) -> Self;
+
+    fn is_zero(&self) -> bool {
+        *self == Self::from_u32(0)
+    }
+}
+
+macro_rules! primitive_quantities {
+    ($($ty:ty),*) => {
+        $(impl Quantity for $ty {
+            fn from_u32(n: u32) -> Self {
+                n.into()
+            }
+
+            fn checked_add(&self, other: &Self) -> Option<Self> {
+                <$ty>::checked_add(*self, *other)
+            }
+
+            fn checked_sub(&self, other: &Self) -> Option<Self> {
+                <$ty>::checked_sub(*self, *other)
+            }
+
+            fn div(&self, divisor: &Self) -> Self {
+                self / divisor
+            }
+
+            fn rem(&self, divisor: &Self) -> Self {
+                self % divisor
+            }
+        })*
+    };
+}
+
+primitive_quantities!(u32, u64, u128);
+
+#[cfg(feature = "bigint")]
+impl Quantity for num_bigint::BigUint {
+    fn from_u32(n: u32) -> Self {
+        n.into()
+    }
+
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        (self >= other).then(|| self - other)
+    }
+
+    fn div(&self, divisor: &Self) -> Self {
+        self / divisor
+    }
+
+    fn rem(&self, divisor: &Self) -> Self {
+        self % divisor
+    }
+}
+
+pub fn calculate_fuel<T: Quantity>(mass: &T) -> T {
+    FuelModel::PART1
+        .fuel(mass)
+        .expect("fuel is less than the mass")
+}
+
+/// Fuel for the mass, then fuel for that fuel and so on, until a stage
+/// needs none.
+pub fn calculate_fuel_recursive<T: Quantity>(mass: &T) -> T {
+    FuelModel::PART2
+        .fuel(mass)
+        .expect("recursive fuel is less than half the mass")
+}
+
+fn add<T: Quantity>(total: &T, fuel: &T) -> Result<T> {
+    total.checked_add(fuel).ok_or_else(|| {
+        anyhow!(
+            "Total fuel overflows {}, use a wider type",
+            std::any::type_name::<T>()
+        )
+    })
+}
+
+/// Adds up `amounts`, failing instead of overflowing.
+pub fn checked_sum<'a, T: Quantity + 'a>(amounts: impl IntoIterator<Item = &'a T>) -> Result<T> {
+    amounts
+        .into_iter()
+        .try_fold(T::from_u32(0), |total, amount| add(&total, amount))
+}
+
+/// Fuel for all `masses` in `model`.
+pub fn total_fuel<'a, T: Quantity + 'a>(
+    masses: impl IntoIterator<Item = &'a T>,
+    model: &FuelModel,
+) -> Result<T> {
+    masses.into_iter().try_fold(T::from_u32(0), |total, mass| {
+        add(&total, &model.fuel(mass)?)
+    })
+}
+
+/// Like `total_fuel` for one mass per line of `reader`, which is read line
+/// by line so module lists of any size fit in memory.
+pub fn total_fuel_streamed<T: Quantity>(reader: impl BufRead, model: &FuelModel) -> Result<T> {
+    let mut total = T::from_u32(0);
+    for_each_mass(reader, |mass: T| {
+        total = add(&total, &model.fuel(&mass)?)?;
+        Ok(())
+    })?;
+    Ok(total)
+}
+
+/// Hands the mass on every line of `reader` to `module` as soon as it's
+/// read, skipping blank lines.
+pub fn for_each_mass<T: Quantity>(
+    mut reader: impl BufRead,
+    mut module: impl FnMut(T) -> Result<()>,
+) -> Result<()> {
+    let mut line = String::new();
+    let mut number = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        number += 1;
+        let mass = line.trim();
+        if mass.is_empty() {
+            continue;
+        }
+        let mass: T = mass.parse().map_err(|err: T::Err| ParseError {
+            file: None,
+            line: number,
+            column: line.len() - line.trim_start().len() + 1,
+            token: mass.to_string(),
+            reason: err.to_string(),
+        })?;
+        module(mass)?;
+    }
+}
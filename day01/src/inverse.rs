@@ -0,0 +1,87 @@
+use crate::model::FuelModel;
+use anyhow::Result;
+use std::ops::RangeInclusive;
+
+/*
+The rocket equation backwards: how heavy can a module be for a given fuel
+budget? Fuel never decreases as the mass grows (every stage is rounded,
+offset and clamped the same way, and a larger stage needs at least as much
+fuel), so the masses that fit are `0..=limit` and the limit is found by
+binary search over the whole of u64, about 64 fuel calculations. Fuel is
+worked out in u128 on the way, so masses whose fuel overflows u64 are simply
+too heavy.
+*/
+
+/// The heaviest module that fits a budget, with the fuel on either side of
+/// the limit as proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MassLimit {
+    pub mass: u64,
+    /// At most the budget.
+    pub fuel: u64,
+    /// Fuel for `mass + 1`, above the budget and maybe above u64::MAX.
+    /// `None` if `mass` is u64::MAX.
+    pub next_fuel: Option<u128>,
+}
+
+/// Fuel for `mass`, a u64 mass can't overflow it.
+fn wide_fuel(mass: u64, model: &FuelModel) -> Result<u128> {
+    model.fuel(&u128::from(mass))
+}
+
+/// The lightest mass that needs more than `fuel`, `None` if no u64 does.
+fn first_above(fuel: u64, model: &FuelModel) -> Result<Option<u64>> {
+    let fuel = u128::from(fuel);
+    if wide_fuel(u64::MAX, model)? <= fuel {
+        return Ok(None);
+    }
+    // fuel(low - 1) <= fuel < fuel(high)
+    let (mut low, mut high) = (0, u64::MAX);
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if wide_fuel(middle, model)? > fuel {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+    Ok(Some(low))
+}
+
+/// The heaviest module whose fuel is at most `budget`. `None` if not even
+/// an empty module fits, which takes a model that fuels nothing.
+pub fn max_mass(budget: u64, model: &FuelModel) -> Result<Option<MassLimit>> {
+    let mass = match first_above(budget, model)? {
+        Some(0) => return Ok(None),
+        Some(above) => above - 1,
+        None => u64::MAX,
+    };
+    Ok(Some(MassLimit {
+        mass,
+        fuel: model.fuel(&mass)?,
+        next_fuel: mass
+            .checked_add(1)
+            .map(|next| wide_fuel(next, model))
+            .transpose()?,
+    }))
+}
+
+/// The heaviest mass `count` equally heavy modules can have together within
+/// `budget`.
+pub fn max_masses(budget: u64, count: u64, model: &FuelModel) -> Result<Option<MassLimit>> {
+    max_mass(budget / count.max(1), model)
+}
+
+/// Every mass that needs exactly `fuel`, `None` if the fuel jumps over it.
+pub fn masses_needing(fuel: u64, model: &FuelModel) -> Result<Option<RangeInclusive<u64>>> {
+    let lightest = match fuel.checked_sub(1) {
+        Some(below) => match first_above(below, model)? {
+            Some(mass) => mass,
+            None => return Ok(None),
+        },
+        None => 0,
+    };
+    Ok(max_mass(fuel, model)?
+        .filter(|limit| limit.mass >= lightest && limit.fuel == fuel)
+        .map(|limit| lightest..=limit.mass))
+}
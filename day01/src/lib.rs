@@ -0,0 +1,37 @@
+use anyhow::Result;
+use aoc_common::{parse, Solution};
+
+/*
+https://adventofcode.com/2019/day/01
+*/
+
+pub mod cli;
+pub mod fuel;
+pub mod inverse;
+pub mod model;
+pub mod report;
+
+pub use fuel::{calculate_fuel, calculate_fuel_recursive, total_fuel};
+pub use model::FuelModel;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<u64>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::lines(input)?)
+    }
+
+    fn part1(masses: &Self::Input) -> Result<u64> {
+        total_fuel(masses, &FuelModel::PART1)
+    }
+
+    fn part2(masses: &Self::Input) -> Result<u64> {
+        total_fuel(masses, &FuelModel::PART2)
+    }
+}
@@ -0,0 +1,220 @@
+use crate::fuel::Quantity;
+use anyhow::{anyhow, bail, Context, Result};
+use aoc_common::ParseError;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/*
+The rocket equation with its constants pulled out, for what-if analyses of
+other engines: a mass needs `mass / divisor - offset` fuel (rounded as
+configured), amounts below `minimum` need none, and with `recursive` the fuel
+needs fuel of its own. The puzzle is `PART1` and `PART2`.
+
+Models are read from files of `key: value` lines, keys that are left out
+keep the value of the model the file is loaded on top of:
+
+    # heavier engines
+    divisor: 4
+    offset: 1
+    rounding: up
+    minimum: 5
+    recursive: true
+*/
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    Down,
+    Up,
+    Nearest,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FuelModel {
+    pub divisor: u32,
+    /// Subtracted after dividing, negative offsets add fuel.
+    pub offset: i32,
+    pub rounding: Rounding,
+    pub minimum: u32,
+    pub recursive: bool,
+}
+
+impl FuelModel {
+    pub const PART1: FuelModel = FuelModel {
+        divisor: 3,
+        offset: 2,
+        rounding: Rounding::Down,
+        minimum: 1,
+        recursive: false,
+    };
+
+    pub const PART2: FuelModel = FuelModel {
+        recursive: true,
+        ..FuelModel::PART1
+    };
+
+    /// Reads the model in `path`, on top of `base`.
+    pub fn load(path: &Path, base: FuelModel) -> Result<Self> {
+        let config = fs::read_to_string(path)
+            .with_context(|| format!("Could not read {}", path.display()))?;
+        base.configure(&config)
+            .map_err(|err| err.in_file(&path.display().to_string()).into())
+    }
+
+    /// This model with the settings in `config` applied.
+    pub fn configure(mut self, config: &str) -> Result<Self, ParseError> {
+        for line in config.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| ParseError::at(config, line, "expected <key>: <value>"))?;
+            let (key, value) = (key.trim(), value.trim());
+            let invalid = |reason: &str| ParseError::at(config, value, reason);
+            match key {
+                "divisor" => {
+                    self.divisor = value.parse().map_err(|_| invalid("expected a number"))?;
+                    if self.divisor == 0 {
+                        return Err(invalid("the divisor can't be 0"));
+                    }
+                }
+                "offset" => {
+                    self.offset = value.parse().map_err(|_| invalid("expected a number"))?
+                }
+                "minimum" => {
+                    self.minimum = value.parse().map_err(|_| invalid("expected a number"))?
+                }
+                "rounding" => {
+                    self.rounding = match value {
+                        "down" => Rounding::Down,
+                        "up" => Rounding::Up,
+                        "nearest" => Rounding::Nearest,
+                        _ => return Err(invalid("expected down, up or nearest")),
+                    }
+                }
+                "recursive" => {
+                    self.recursive = value
+                        .parse()
+                        .map_err(|_| invalid("expected true or false"))?
+                }
+                _ => {
+                    return Err(ParseError::at(
+                        config,
+                        key,
+                        "expected divisor, offset, rounding, minimum or recursive",
+                    ))
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Fuel for `mass` alone, without fuel for the fuel. Models built
+    /// without `configure` may have a divisor of 0, that's an error here.
+    pub fn direct_fuel<T: Quantity>(&self, mass: &T) -> Result<T> {
+        if self.divisor == 0 {
+            bail!("Can't divide {mass} by a divisor of 0");
+        }
+        let overflow = || anyhow!("Fuel for {mass} overflows {}", std::any::type_name::<T>());
+        let divisor = T::from_u32(self.divisor);
+        let quotient = mass.div(&divisor);
+        let remainder = mass.rem(&divisor);
+        let round_up = match self.rounding {
+            Rounding::Down => false,
+            Rounding::Up => !remainder.is_zero(),
+            Rounding::Nearest => divisor
+                .checked_sub(&remainder)
+                .is_some_and(|rest| remainder >= rest),
+        };
+        let quotient = if round_up {
+            quotient.checked_add(&T::from_u32(1)).ok_or_else(overflow)?
+        } else {
+            quotient
+        };
+        let offset = T::from_u32(self.offset.unsigned_abs());
+        let fuel = if self.offset >= 0 {
+            quotient
+                .checked_sub(&offset)
+                .unwrap_or_else(|| T::from_u32(0))
+        } else {
+            quotient.checked_add(&offset).ok_or_else(overflow)?
+        };
+        if fuel < T::from_u32(self.minimum) {
+            return Ok(T::from_u32(0));
+        }
+        Ok(fuel)
+    }
+
+    /// Hands every stage of fuel for `mass` to `stage`: the direct fuel,
+    /// then (if `recursive`) the fuel for the previous stage, as long as
+    /// it's not zero.
+    fn for_each_stage<T: Quantity>(
+        &self,
+        mass: &T,
+        mut stage: impl FnMut(&T) -> Result<()>,
+    ) -> Result<()> {
+        let mut previous = self.direct_fuel(mass)?;
+        if previous.is_zero() {
+            return Ok(());
+        }
+        stage(&previous)?;
+        if !self.recursive {
+            return Ok(());
+        }
+        loop {
+            let next = self.direct_fuel(&previous)?;
+            if next.is_zero() {
+                return Ok(());
+            }
+            // Stages must shrink, otherwise fuel would need ever more fuel.
+            if next >= previous {
+                bail!("The fuel of {mass} never runs out with {self}: {previous} fuel needs {next} more");
+            }
+            stage(&next)?;
+            previous = next;
+        }
+    }
+
+    /// Fuel for `mass`, and for the fuel stage by stage if `recursive`.
+    pub fn fuel<T: Quantity>(&self, mass: &T) -> Result<T> {
+        let mut total = T::from_u32(0);
+        self.for_each_stage(mass, |stage| {
+            total = total.checked_add(stage).ok_or_else(|| {
+                anyhow!("Fuel for {mass} overflows {}", std::any::type_name::<T>())
+            })?;
+            Ok(())
+        })?;
+        Ok(total)
+    }
+
+    /// The amounts `fuel` adds up, direct fuel first.
+    pub fn stages<T: Quantity>(&self, mass: &T) -> Result<Vec<T>> {
+        let mut stages = Vec::new();
+        self.for_each_stage(mass, |stage| {
+            stages.push(stage.clone());
+            Ok(())
+        })?;
+        Ok(stages)
+    }
+}
+
+impl fmt::Display for FuelModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rounding = match self.rounding {
+            Rounding::Down => "down",
+            Rounding::Up => "up",
+            Rounding::Nearest => "to nearest",
+        };
+        write!(f, "mass / {} rounded {rounding}", self.divisor)?;
+        match self.offset {
+            offset if offset < 0 => write!(f, " + {}", offset.unsigned_abs())?,
+            offset => write!(f, " - {offset}")?,
+        }
+        write!(f, ", at least {}", self.minimum)?;
+        if self.recursive {
+            write!(f, ", recursive")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,334 @@
+use crate::fuel::{checked_sum, for_each_mass, Quantity};
+use crate::model::FuelModel;
+use anyhow::{bail, Result};
+use serde_json::json;
+use std::fmt;
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+
+/*
+Where the fuel goes: every module with its direct fuel, the fuel for that
+fuel stage by stage and its total, followed by statistics over the module
+totals and a histogram of them.
+
+Reports are written module by module as the masses are read, in any
+`Quantity`, so they work for inputs of any size and never overflow with
+bigger types. Only the module totals are kept, for the statistics. JSON
+numbers are written digit for digit, however big they get.
+*/
+
+pub const HISTOGRAM_BUCKETS: u32 = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleFuel<T> {
+    pub mass: T,
+    /// Direct fuel first, then the fuel for each previous stage.
+    pub stages: Vec<T>,
+    pub total: T,
+}
+
+impl<T: Quantity> ModuleFuel<T> {
+    pub fn new(mass: T, model: &FuelModel) -> Result<Self> {
+        let stages = model.stages(&mass)?;
+        let total = checked_sum(&stages)?;
+        Ok(ModuleFuel {
+            mass,
+            stages,
+            total,
+        })
+    }
+
+    fn direct(&self) -> T {
+        self.stages
+            .first()
+            .cloned()
+            .unwrap_or_else(|| T::from_u32(0))
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let stages: Vec<serde_json::Value> = self.stages.iter().map(number).collect();
+        json!({
+            "mass": number(&self.mass),
+            "direct": number(&self.direct()),
+            "stages": stages,
+            "total": number(&self.total),
+        })
+    }
+}
+
+/// Module totals in `from..=to`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bucket<T> {
+    pub from: T,
+    pub to: T,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats<T> {
+    pub min: T,
+    pub max: T,
+    pub mean: f64,
+    pub histogram: Vec<Bucket<T>>,
+}
+
+impl<T: Quantity> Stats<T> {
+    fn to_json(&self) -> serde_json::Value {
+        let histogram: Vec<serde_json::Value> = self
+            .histogram
+            .iter()
+            .map(|bucket| {
+                json!({"from": number(&bucket.from), "to": number(&bucket.to), "count": bucket.count})
+            })
+            .collect();
+        json!({
+            "min": number(&self.min),
+            "max": number(&self.max),
+            "mean": self.mean,
+            "histogram": histogram,
+        })
+    }
+}
+
+/// `value` as a JSON number, however many digits it has.
+fn number<T: Quantity>(value: &T) -> serde_json::Value {
+    serde_json::from_str(&value.to_string()).expect("quantities are written as digits")
+}
+
+/// A report held in memory, for callers that want to look at the modules.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report<T = u64> {
+    pub modules: Vec<ModuleFuel<T>>,
+    pub total: T,
+    /// `None` without modules.
+    pub stats: Option<Stats<T>>,
+}
+
+impl<T: Quantity> Report<T> {
+    pub fn new(masses: &[T], model: &FuelModel) -> Result<Self> {
+        let modules = masses
+            .iter()
+            .map(|mass| ModuleFuel::new(mass.clone(), model))
+            .collect::<Result<Vec<ModuleFuel<T>>>>()?;
+        let totals: Vec<T> = modules.iter().map(|module| module.total.clone()).collect();
+        Ok(Report {
+            total: checked_sum(&totals)?,
+            stats: stats(&totals),
+            modules,
+        })
+    }
+
+    pub fn render(&self, format: ReportFormat) -> Result<String> {
+        let mut writer = ReportWriter::new(Vec::new(), format)?;
+        for module in &self.modules {
+            writer.module(module.clone())?;
+        }
+        Ok(String::from_utf8(writer.finish()?)?)
+    }
+
+    pub fn to_csv(&self) -> String {
+        self.render(ReportFormat::Csv)
+            .expect("reports render to memory")
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let modules: Vec<serde_json::Value> =
+            self.modules.iter().map(ModuleFuel::to_json).collect();
+        json!({
+            "modules": modules,
+            "total": number(&self.total),
+            "stats": self.stats.as_ref().map(Stats::to_json),
+        })
+    }
+}
+
+impl<T: Quantity> fmt::Display for Report<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = self.render(ReportFormat::Text).map_err(|_| fmt::Error)?;
+        f.write_str(&text)
+    }
+}
+
+/// Streams the report on the masses in `reader`, one per line, to `out`.
+pub fn stream_report<T: Quantity>(
+    reader: impl BufRead,
+    model: &FuelModel,
+    format: ReportFormat,
+    out: impl Write,
+) -> Result<()> {
+    let mut writer = ReportWriter::new(out, format)?;
+    for_each_mass(reader, |mass: T| {
+        writer.module(ModuleFuel::new(mass, model)?)
+    })?;
+    writer.finish()?;
+    Ok(())
+}
+
+/// Writes a report module by module, the summary once all are in.
+pub struct ReportWriter<W: Write, T> {
+    out: W,
+    format: ReportFormat,
+    totals: Vec<T>,
+}
+
+impl<W: Write, T: Quantity> ReportWriter<W, T> {
+    pub fn new(mut out: W, format: ReportFormat) -> Result<Self> {
+        match format {
+            ReportFormat::Text => writeln!(
+                out,
+                "{:>10}  {:>10}  {:>10}  Stages",
+                "Mass", "Direct", "Total"
+            )?,
+            ReportFormat::Csv => writeln!(out, "mass,direct,stages,total")?,
+            ReportFormat::Json => write!(out, "{{\n  \"modules\": [")?,
+        }
+        Ok(ReportWriter {
+            out,
+            format,
+            totals: Vec::new(),
+        })
+    }
+
+    pub fn module(&mut self, module: ModuleFuel<T>) -> Result<()> {
+        let stages: Vec<String> = module.stages.iter().map(T::to_string).collect();
+        match self.format {
+            ReportFormat::Text => writeln!(
+                self.out,
+                "{:>10}  {:>10}  {:>10}  {}",
+                module.mass,
+                module.direct(),
+                module.total,
+                stages.join(" + ")
+            )?,
+            ReportFormat::Csv => writeln!(
+                self.out,
+                "{},{},{},{}",
+                module.mass,
+                module.direct(),
+                stages.join(";"),
+                module.total
+            )?,
+            ReportFormat::Json => {
+                let separator = if self.totals.is_empty() { "" } else { "," };
+                write!(self.out, "{separator}\n    {}", module.to_json())?;
+            }
+        }
+        self.totals.push(module.total);
+        Ok(())
+    }
+
+    /// Writes the summary and hands back the output.
+    pub fn finish(mut self) -> Result<W> {
+        let total = checked_sum(&self.totals)?;
+        let stats = stats(&self.totals);
+        match self.format {
+            ReportFormat::Text => self.text_summary(&total, stats.as_ref())?,
+            ReportFormat::Csv => {}
+            ReportFormat::Json => {
+                let stats = stats.as_ref().map(Stats::to_json);
+                let end = if self.totals.is_empty() { "" } else { "\n  " };
+                // Unlike the modules, the stats are small enough to spread out.
+                let stats = serde_json::to_string_pretty(&serde_json::Value::from(stats))?;
+                write!(
+                    self.out,
+                    "{end}],\n  \"total\": {},\n  \"stats\": {}\n}}\n",
+                    number(&total),
+                    stats.replace('\n', "\n  ")
+                )?;
+            }
+        }
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    fn text_summary(&mut self, total: &T, stats: Option<&Stats<T>>) -> Result<()> {
+        writeln!(self.out)?;
+        writeln!(self.out, "Modules: {}", self.totals.len())?;
+        writeln!(self.out, "Total fuel: {total}")?;
+        let Some(stats) = stats else {
+            return Ok(());
+        };
+        writeln!(
+            self.out,
+            "Per module: min {}, max {}, mean {:.1}",
+            stats.min, stats.max, stats.mean
+        )?;
+        let widest = stats.histogram.iter().map(|bucket| bucket.count).max();
+        for bucket in &stats.histogram {
+            // Bars are at most 40 characters long.
+            let bar = bucket.count * 40 / widest.unwrap_or(1).max(1);
+            let row = format!(
+                "{:>10} - {:<10}  {:>6}  {}",
+                bucket.from,
+                bucket.to,
+                bucket.count,
+                "#".repeat(bar)
+            );
+            writeln!(self.out, "{}", row.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+fn stats<T: Quantity>(totals: &[T]) -> Option<Stats<T>> {
+    let (min, max) = (totals.iter().min()?, totals.iter().max()?);
+    let sum: f64 = totals
+        .iter()
+        .map(|total| total.to_string().parse::<f64>().unwrap_or(f64::INFINITY))
+        .sum();
+    Some(Stats {
+        min: min.clone(),
+        max: max.clone(),
+        mean: sum / totals.len() as f64,
+        histogram: histogram(totals, min, max),
+    })
+}
+
+/// Up to `HISTOGRAM_BUCKETS` buckets of equal width from `min` to `max`.
+fn histogram<T: Quantity>(totals: &[T], min: &T, max: &T) -> Vec<Bucket<T>> {
+    let spread = max.checked_sub(min).expect("min is at most max");
+    let last = spread.div(&T::from_u32(HISTOGRAM_BUCKETS));
+    let width = last
+        .checked_add(&T::from_u32(1))
+        .expect("a tenth of the spread is not the largest quantity");
+    let mut histogram: Vec<Bucket<T>> = Vec::new();
+    let mut from = Some(min.clone());
+    while let Some(start) = from.filter(|start| start <= max) {
+        if histogram.len() == HISTOGRAM_BUCKETS as usize {
+            break;
+        }
+        from = start.checked_add(&width);
+        histogram.push(Bucket {
+            to: start
+                .checked_add(&last)
+                .map_or(max.clone(), |to| to.min(max.clone())),
+            from: start,
+            count: 0,
+        });
+    }
+    for total in totals {
+        let bucket = histogram.partition_point(|bucket| bucket.from <= *total) - 1;
+        histogram[bucket].count += 1;
+    }
+    histogram
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Text,
+    Csv,
+    Json,
+}
+
+impl FromStr for ReportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(format: &str) -> Result<Self> {
+        match format {
+            "text" => Ok(ReportFormat::Text),
+            "csv" => Ok(ReportFormat::Csv),
+            "json" => Ok(ReportFormat::Json),
+            _ => bail!("Unknown report format {format:?}, expected text, csv or json"),
+        }
+    }
+}
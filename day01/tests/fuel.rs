@@ -0,0 +1,84 @@
+use day01::fuel::{total_fuel, total_fuel_streamed};
+use day01::{calculate_fuel, calculate_fuel_recursive, FuelModel};
+use std::io::{BufReader, Read};
+
+#[test]
+fn fuel_is_the_same_in_every_width() {
+    assert_eq!(calculate_fuel(&1969u32), 654);
+    assert_eq!(calculate_fuel(&2u64), 0);
+    assert_eq!(calculate_fuel_recursive(&100756u128), 50346);
+    assert_eq!(calculate_fuel_recursive(&14u64), 2);
+}
+
+#[test]
+fn recursive_fuel_of_huge_masses_needs_no_stack() {
+    // About 80 fuel stages, each one a third of the one before.
+    let mass = u128::MAX;
+    let fuel = calculate_fuel_recursive(&mass);
+    assert!(fuel < mass / 2 && fuel > mass / 3);
+}
+
+#[test]
+fn totals_that_overflow_are_errors() {
+    let masses = vec![u32::MAX; 4];
+    let err = total_fuel(&masses, &FuelModel::PART1).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Total fuel overflows u32, use a wider type"
+    );
+
+    let wide: Vec<u64> = masses.iter().map(|&mass| mass.into()).collect();
+    assert_eq!(
+        total_fuel(&wide, &FuelModel::PART1).unwrap(),
+        4 * (u64::from(u32::MAX) / 3 - 2)
+    );
+}
+
+/// 100756 repeated `count` times, one per line, produced on the fly.
+struct Modules {
+    remaining: usize,
+    pending: &'static [u8],
+}
+
+impl Read for Modules {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.pending.is_empty() {
+            if self.remaining == 0 {
+                return Ok(0);
+            }
+            self.remaining -= 1;
+            self.pending = b"100756\n";
+        }
+        let n = self.pending.len().min(buf.len());
+        buf[..n].copy_from_slice(&self.pending[..n]);
+        self.pending = &self.pending[n..];
+        Ok(n)
+    }
+}
+
+#[test]
+fn streamed_totals_match_and_report_bad_lines() {
+    let modules = Modules {
+        remaining: 100_000,
+        pending: b"",
+    };
+    let total: u64 = total_fuel_streamed(BufReader::new(modules), &FuelModel::PART2).unwrap();
+    assert_eq!(total, 50346 * 100_000);
+
+    let err =
+        total_fuel_streamed::<u64>("12\n\n  -14\n".as_bytes(), &FuelModel::PART1).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "3:3: invalid token \"-14\": invalid digit found in string"
+    );
+}
+
+#[cfg(feature = "bigint")]
+#[test]
+fn big_integers_never_overflow() {
+    use num_bigint::BigUint;
+
+    let masses = vec![BigUint::from(u128::MAX); 3];
+    let total = total_fuel(&masses, &FuelModel::PART2).unwrap();
+    assert!(total > BigUint::from(u128::MAX));
+}
@@ -0,0 +1,98 @@
+use day01::calculate_fuel_recursive;
+use day01::inverse::{masses_needing, max_mass, max_masses, MassLimit};
+use day01::model::{FuelModel, Rounding};
+
+#[test]
+fn the_limit_is_the_heaviest_mass_that_fits() {
+    assert_eq!(
+        max_mass(50346, &FuelModel::PART2).unwrap(),
+        Some(MassLimit {
+            mass: 100757,
+            fuel: 50346,
+            next_fuel: Some(50347),
+        })
+    );
+    // Small modules need no fuel at all.
+    assert_eq!(max_mass(0, &FuelModel::PART2).unwrap().unwrap().mass, 8);
+    // 100 modules share the budget.
+    assert_eq!(
+        max_masses(5034600, 100, &FuelModel::PART2)
+            .unwrap()
+            .unwrap()
+            .mass,
+        100757
+    );
+
+    let limit = max_mass(u64::MAX, &FuelModel::PART2).unwrap().unwrap();
+    assert_eq!((limit.mass, limit.next_fuel), (u64::MAX, None));
+}
+
+#[test]
+fn answers_match_a_brute_force_search() {
+    let fuel: Vec<u64> = (0..3000u64)
+        .map(|mass| calculate_fuel_recursive(&mass))
+        .collect();
+    for budget in 0..900 {
+        let heaviest = fuel.iter().rposition(|&fuel| fuel <= budget).unwrap() as u64;
+        let limit = max_mass(budget, &FuelModel::PART2).unwrap().unwrap();
+        assert_eq!(limit.mass, heaviest, "budget {budget}");
+
+        let exact: Vec<u64> = (0..3000)
+            .filter(|&mass| fuel[mass as usize] == budget)
+            .collect();
+        let masses = masses_needing(budget, &FuelModel::PART2).unwrap();
+        match (exact.first(), exact.last()) {
+            (Some(&first), Some(&last)) => assert_eq!(masses, Some(first..=last)),
+            _ => assert_eq!(masses, None, "budget {budget}"),
+        }
+    }
+}
+
+#[test]
+fn other_models_are_searched_the_same_way() {
+    let model = FuelModel {
+        divisor: 2,
+        offset: 0,
+        rounding: Rounding::Up,
+        minimum: 0,
+        recursive: true,
+    };
+    // Rounding up never lets the fuel reach zero.
+    let err = max_mass(10, &model).unwrap_err().to_string();
+    assert!(err.contains("never runs out"), "{err}");
+
+    let heavier = FuelModel {
+        offset: -1,
+        ..FuelModel::PART1
+    };
+    // Even an empty module needs 1 fuel.
+    assert_eq!(max_mass(0, &heavier).unwrap(), None);
+    assert_eq!(masses_needing(1, &heavier).unwrap(), Some(0..=2));
+}
+
+#[test]
+fn fuel_beyond_u64_is_over_any_budget() {
+    // Fuel is the mass plus 3, the heaviest masses overflow a u64.
+    let model = FuelModel {
+        divisor: 1,
+        offset: -3,
+        rounding: Rounding::Down,
+        minimum: 0,
+        recursive: false,
+    };
+    assert!(model.fuel(&u64::MAX).is_err());
+    assert_eq!(
+        max_mass(100, &model).unwrap(),
+        Some(MassLimit {
+            mass: 97,
+            fuel: 100,
+            next_fuel: Some(101),
+        })
+    );
+    let limit = max_mass(u64::MAX, &model).unwrap().unwrap();
+    assert_eq!(
+        (limit.mass, limit.next_fuel),
+        (u64::MAX - 3, Some(u128::from(u64::MAX) + 1))
+    );
+    assert_eq!(masses_needing(2, &model).unwrap(), None);
+}
@@ -0,0 +1,88 @@
+use day01::model::{FuelModel, Rounding};
+
+#[test]
+fn the_puzzle_models_match_the_examples() {
+    assert_eq!(FuelModel::PART1.fuel(&1969u64).unwrap(), 654);
+    assert_eq!(FuelModel::PART2.fuel(&1969u64).unwrap(), 966);
+    assert_eq!(FuelModel::PART2.fuel(&100756u64).unwrap(), 50346);
+}
+
+#[test]
+fn rounding_offset_and_minimum_are_applied_in_order() {
+    let model = |rounding| FuelModel {
+        divisor: 4,
+        offset: 1,
+        rounding,
+        minimum: 3,
+        recursive: false,
+    };
+    // 18 / 4 = 4.5
+    assert_eq!(model(Rounding::Down).fuel(&18u32).unwrap(), 3);
+    assert_eq!(model(Rounding::Up).fuel(&18u32).unwrap(), 4);
+    assert_eq!(model(Rounding::Nearest).fuel(&18u32).unwrap(), 4);
+    assert_eq!(model(Rounding::Nearest).fuel(&17u32).unwrap(), 3);
+    // 2 fuel is below the minimum of 3
+    assert_eq!(model(Rounding::Down).fuel(&12u32).unwrap(), 0);
+
+    let heavier = FuelModel {
+        offset: -1,
+        ..FuelModel::PART1
+    };
+    assert_eq!(heavier.fuel(&12u32).unwrap(), 5);
+}
+
+#[test]
+fn models_that_never_converge_are_errors() {
+    let model = FuelModel {
+        divisor: 1,
+        offset: 0,
+        ..FuelModel::PART2
+    };
+    let err = model.fuel(&10u64).unwrap_err().to_string();
+    assert!(err.contains("never runs out"), "{err}");
+}
+
+#[test]
+fn configs_override_the_base_model() {
+    let config = "\
+# heavier engines
+divisor: 4
+rounding: up
+
+recursive: true
+";
+    let model = FuelModel::PART1.configure(config).unwrap();
+    assert_eq!(
+        model,
+        FuelModel {
+            divisor: 4,
+            rounding: Rounding::Up,
+            recursive: true,
+            ..FuelModel::PART1
+        }
+    );
+
+    let err = FuelModel::PART1
+        .configure("divisor: 4\nrounding: sideways")
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "2:11: invalid token \"sideways\": expected down, up or nearest"
+    );
+    let err = FuelModel::PART1.configure("divisor: 0").unwrap_err();
+    assert_eq!(err.reason, "the divisor can't be 0");
+    let err = FuelModel::PART1.configure("speed: 3").unwrap_err();
+    assert_eq!((err.line, err.column, err.token.as_str()), (1, 1, "speed"));
+}
+
+#[test]
+fn a_divisor_of_zero_is_an_error_not_a_panic() {
+    let model = FuelModel {
+        divisor: 0,
+        ..FuelModel::PART1
+    };
+    let err = model.fuel(&12u64).unwrap_err();
+    assert_eq!(err.to_string(), "Can't divide 12 by a divisor of 0");
+    assert!(model.stages(&12u64).is_err());
+    assert!(day01::inverse::max_mass(100, &model).is_err());
+}
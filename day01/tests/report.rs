@@ -0,0 +1,114 @@
+use day01::report::{stream_report, Bucket, Report, ReportFormat};
+use day01::FuelModel;
+
+#[test]
+fn modules_list_every_stage_of_their_fuel() {
+    let report = Report::<u64>::new(&[14, 1969], &FuelModel::PART2).unwrap();
+    assert_eq!(report.modules[0].stages, vec![2]);
+    assert_eq!(report.modules[1].stages, vec![654, 216, 70, 21, 5]);
+    assert_eq!(report.modules[1].total, 966);
+    assert_eq!(report.total, 968);
+
+    let direct = Report::<u64>::new(&[1969], &FuelModel::PART1).unwrap();
+    assert_eq!(direct.modules[0].stages, vec![654]);
+}
+
+#[test]
+fn stats_cover_the_module_totals() {
+    let report = Report::<u64>::new(&[12, 14, 1969, 100756], &FuelModel::PART2).unwrap();
+    let stats = report.stats.unwrap();
+    assert_eq!((stats.min, stats.max), (2, 50346));
+    assert_eq!(stats.mean, 51316.0 / 4.0);
+    assert_eq!(stats.histogram.len(), 10);
+    assert_eq!(
+        stats.histogram.first(),
+        Some(&Bucket {
+            from: 2,
+            to: 5036,
+            count: 3
+        })
+    );
+    assert_eq!(stats.histogram.last().map(|bucket| bucket.count), Some(1));
+    assert_eq!(stats.histogram.last().map(|bucket| bucket.to), Some(50346));
+
+    let single = Report::<u64>::new(&[14, 14], &FuelModel::PART2).unwrap();
+    assert_eq!(single.stats.unwrap().histogram.len(), 1);
+    assert_eq!(
+        Report::<u64>::new(&[], &FuelModel::PART2).unwrap().stats,
+        None
+    );
+}
+
+#[test]
+fn reports_export_to_csv_and_json() {
+    let report = Report::<u64>::new(&[14, 1969], &FuelModel::PART2).unwrap();
+    assert_eq!(
+        report.render(ReportFormat::Csv).unwrap(),
+        "mass,direct,stages,total\n14,2,2,2\n1969,654,654;216;70;21;5,966\n"
+    );
+    let json = report.to_json();
+    assert_eq!(json["total"], 968);
+    assert_eq!(json["modules"][1]["stages"][4], 5);
+    assert_eq!(json["stats"]["histogram"][0]["count"], 1);
+}
+
+fn streamed<T: day01::fuel::Quantity>(input: &str, format: ReportFormat) -> String {
+    let mut out = Vec::new();
+    stream_report::<T>(input.as_bytes(), &FuelModel::PART2, format, &mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn streamed_reports_match_the_ones_in_memory() {
+    let report = Report::<u64>::new(&[12, 14, 1969, 100756], &FuelModel::PART2).unwrap();
+    for format in [ReportFormat::Text, ReportFormat::Csv, ReportFormat::Json] {
+        assert_eq!(
+            streamed::<u64>("12\n14\n\n1969\n100756\n", format),
+            report.render(format).unwrap()
+        );
+    }
+    let json: serde_json::Value =
+        serde_json::from_str(&streamed::<u64>("", ReportFormat::Json)).unwrap();
+    assert_eq!(json["modules"].as_array().map(Vec::len), Some(0));
+    assert!(json["stats"].is_null());
+}
+
+#[test]
+fn streamed_reports_add_up_in_wider_types() {
+    // Four modules whose fuel adds up to more than u64 holds.
+    let input = format!("{}\n", u64::MAX).repeat(4);
+    let mut out = Vec::new();
+    let overflow = stream_report::<u64>(
+        input.as_bytes(),
+        &FuelModel::PART1,
+        ReportFormat::Csv,
+        &mut out,
+    );
+    assert!(overflow.is_err());
+
+    let mut out = Vec::new();
+    stream_report::<u128>(
+        input.as_bytes(),
+        &FuelModel::PART1,
+        ReportFormat::Json,
+        &mut out,
+    )
+    .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    let total = (u64::MAX / 3 - 2) as u128 * 4;
+    assert_eq!(json["total"].to_string(), total.to_string());
+    assert_eq!(json["stats"]["histogram"][0]["count"], 4);
+}
+
+#[test]
+fn streamed_reports_name_the_line_of_bad_masses() {
+    let mut out = Vec::new();
+    let err = stream_report::<u64>(
+        "12\nheavy\n".as_bytes(),
+        &FuelModel::PART2,
+        ReportFormat::Text,
+        &mut out,
+    )
+    .unwrap_err();
+    assert!(format!("{err:#}").contains("heavy"), "{err:#}");
+}
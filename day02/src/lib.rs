@@ -0,0 +1,158 @@
+use anyhow::{anyhow, Result};
+use aoc_common::{parse, Answer, Solution, Value};
+use std::fmt;
+
+/*
+https://adventofcode.com/2019/day/02
+*/
+
+pub const TARGET_OUTPUT: u32 = 19690720;
+
+#[derive(PartialEq)]
+struct Instruction {
+    identifier: u32,
+    clear_name: &'static str,
+    cycles: u8,
+}
+
+const ADD: Instruction = Instruction {
+    identifier: 1,
+    clear_name: "ADD",
+    cycles: 4,
+};
+
+const MULTIPLY: Instruction = Instruction {
+    identifier: 2,
+    clear_name: "MULTIPLY",
+    cycles: 4,
+};
+
+const HALT: Instruction = Instruction {
+    identifier: 99,
+    clear_name: "HALT",
+    cycles: 1,
+};
+
+const NOOP: Instruction = Instruction {
+    identifier: 0,
+    clear_name: "NOOP",
+    cycles: 1,
+};
+
+fn get_instruction_by_id(id: u32) -> &'static Instruction {
+    match id {
+        1 => &ADD,
+        2 => &MULTIPLY,
+        99 => &HALT,
+        _ => &NOOP,
+    }
+}
+
+/*
+    Add, Multiply are always followed by 3 parameters (index in the array)
+    e.g.
+
+    ADD      Source1 Source2 Destination
+    MULTIPLY Source1 Source2 Destination
+*/
+pub fn run_program(data: &mut [u32]) {
+    let mut pc = 0;
+    while pc < data.len() {
+        let id = data[pc];
+        let instruction = get_instruction_by_id(id);
+        /*
+            Do the lookup and calculations
+        */
+
+        match *instruction {
+            ADD => {
+                let source1 = data[pc + 1] as usize;
+                let source2 = data[pc + 2] as usize;
+                let destination = data[pc + 3] as usize;
+                data[destination] = data[source1] + data[source2];
+            }
+            MULTIPLY => {
+                let source1 = data[pc + 1] as usize;
+                let source2 = data[pc + 2] as usize;
+                let destination = data[pc + 3] as usize;
+                data[destination] = data[source1] * data[source2];
+            }
+            HALT => {
+                break;
+            }
+            _ => panic!("Unknown INSTRUCTION"),
+        }
+
+        pc += instruction.cycles as usize;
+    }
+}
+
+/*
+Noun = data[1]
+Verb = data[2]
+
+0..=99 both ends inclusive
+*/
+pub fn find_noun_verb(initial_data: &[u32], target: u32) -> Option<(u32, u32)> {
+    for noun in 0..=99 {
+        for verb in 0..=99 {
+            let mut data = initial_data.to_vec();
+
+            data[1] = noun;
+            data[2] = verb;
+
+            run_program(&mut data);
+
+            if data[0] == target {
+                return Some((noun, verb));
+            }
+        }
+    }
+    None
+}
+
+/// The inputs that make the program produce the target output, answered
+/// as `100 * noun + verb`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NounVerb {
+    pub noun: u32,
+    pub verb: u32,
+}
+
+impl fmt::Display for NounVerb {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.noun * 100 + self.verb)
+    }
+}
+
+impl Answer for NounVerb {
+    fn diagnostics(&self) -> Vec<(&'static str, Value)> {
+        vec![("noun", self.noun.into()), ("verb", self.verb.into())]
+    }
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = NounVerb;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::comma_separated(input)?)
+    }
+
+    fn part1(initial_data: &Self::Input) -> Result<u32> {
+        let mut data = initial_data.clone();
+        run_program(&mut data);
+        Ok(data[0])
+    }
+
+    fn part2(initial_data: &Self::Input) -> Result<NounVerb> {
+        let (noun, verb) = find_noun_verb(initial_data, TARGET_OUTPUT)
+            .ok_or_else(|| anyhow!("No noun and verb produce {TARGET_OUTPUT}"))?;
+        Ok(NounVerb { noun, verb })
+    }
+}
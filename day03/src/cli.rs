@@ -0,0 +1,167 @@
+use crate::metric::Metric;
+use crate::render::Picture;
+use crate::wire::Delay;
+use crate::wire_set::WireSet;
+use crate::{Point, ORIGIN, PUZZLE_WIRES};
+use anyhow::{bail, Context, Result};
+use aoc_common::parse::{comma_separated, from_str, pair, Parser};
+use aoc_common::{output_paths, Source};
+use std::fs;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+/*
+Options of the day03 binaries, on top of the input paths.
+*/
+
+/// Size of the terminal preview in characters.
+pub const TERMINAL_SIZE: (usize, usize) = (120, 60);
+
+/// What a day binary was asked to do.
+pub struct Options {
+    /// Indices of the wires to compare, the first two by default.
+    pub wires: Vec<usize>,
+    /// Which visits count for the signal delay.
+    pub delay: Delay,
+    /// Where to draw the wires as SVG.
+    pub svg: Option<String>,
+    /// Where to draw them as PNG.
+    pub png: Option<String>,
+    /// Whether to draw them in the terminal too.
+    pub ascii: bool,
+    /// How to rank the crossings instead of answering the puzzle, the
+    /// part's own measure if only `top` or `from` is given.
+    pub metric: Option<Metric>,
+    /// Where distances are measured from, the central port unless given.
+    pub from: Option<Point>,
+    /// How many crossings to rank.
+    pub top: Option<usize>,
+    pub paths: Vec<String>,
+}
+
+/// Parses `--wires <i>,<j>,...`, `--delay <first|last|loop-removed>`,
+/// `--svg <file>`, `--png <file>`, `--ascii`, `--metric <name>`, `--from <x>,<y>`,
+/// `--top <k>` and the input paths of a day binary.
+pub fn parse_args(mut args: impl Iterator<Item = String>, usage: &str) -> Result<Options> {
+    let mut options = Options {
+        wires: PUZZLE_WIRES.to_vec(),
+        delay: Delay::First,
+        svg: None,
+        png: None,
+        ascii: false,
+        metric: None,
+        from: None,
+        top: None,
+        paths: Vec::new(),
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--ascii" => options.ascii = true,
+            "--wires" | "--delay" | "--svg" | "--png" | "--metric" | "--from" | "--top" => {
+                let Some(value) = args.next() else {
+                    bail!("{usage}");
+                };
+                match arg.as_str() {
+                    "--wires" => {
+                        options.wires = comma_separated(&value)
+                            .with_context(|| format!("Invalid --wires {value:?}"))?
+                    }
+                    "--delay" => options.delay = value.parse()?,
+                    "--svg" => options.svg = Some(value),
+                    "--png" => options.png = Some(value),
+                    "--metric" => options.metric = Some(value.parse()?),
+                    "--from" => {
+                        options.from = Some(
+                            pair(",", from_str(), from_str())
+                                .map(|(x, y)| Point::new(x, y))
+                                .parse(&value)
+                                .with_context(|| format!("Invalid --from {value:?}"))?,
+                        )
+                    }
+                    _ => options.top = Some(value.parse()?),
+                }
+            }
+            _ => options.paths.push(arg),
+        }
+    }
+    if let Some(Metric::Delay(_)) = options.metric {
+        options.metric = Some(Metric::Delay(options.delay));
+    }
+    Ok(options)
+}
+
+/// The files one input is drawn to.
+pub struct Drawing {
+    pub svg: Option<PathBuf>,
+    pub png: Option<PathBuf>,
+}
+
+/// The files each of `inputs` is drawn to, named after the input if there
+/// are several.
+pub fn drawings(options: &Options, inputs: &[Source]) -> Result<Vec<Drawing>> {
+    let paths = |path: &Option<String>| -> Result<Vec<Option<PathBuf>>> {
+        match path {
+            Some(path) => Ok(output_paths(path, inputs)?.into_iter().map(Some).collect()),
+            None => Ok(vec![None; inputs.len()]),
+        }
+    };
+    let pngs = paths(&options.png)?;
+    Ok(paths(&options.svg)?
+        .into_iter()
+        .zip(pngs)
+        .map(|(svg, png)| Drawing { svg, png })
+        .collect())
+}
+
+/// Draws the wires as asked for in `options`, to the files in `drawing`.
+/// The crossings are only highlighted if there are wires to compare.
+pub fn draw(set: &WireSet, options: &Options, drawing: &Drawing) -> Result<()> {
+    if drawing.svg.is_none() && drawing.png.is_none() && !options.ascii {
+        return Ok(());
+    }
+    let mut picture = Picture::new(set);
+    if set.len() > 1 {
+        picture = picture.highlight(&options.wires, options.delay)?;
+    }
+    if options.ascii {
+        let (width, height) = TERMINAL_SIZE;
+        print!(
+            "{}",
+            picture.to_terminal(width, height, std::io::stdout().is_terminal())
+        );
+    }
+    if let Some(path) = &drawing.svg {
+        fs::write(path, picture.to_svg())
+            .with_context(|| format!("Could not write {}", path.display()))?;
+    }
+    if let Some(path) = &drawing.png {
+        fs::write(path, picture.to_png()?)
+            .with_context(|| format!("Could not write {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Ranks the crossings if `options` ask for it, by `metric` unless they name
+/// another one. Returns whether they did.
+pub fn rank(set: &WireSet, options: &Options, metric: Metric) -> Result<bool> {
+    if options.metric.is_none() && options.top.is_none() && options.from.is_none() {
+        return Ok(false);
+    }
+    let metric = options.metric.unwrap_or(metric);
+    let from = options.from.unwrap_or(ORIGIN);
+    let crossings = set.best_crossings(&options.wires, metric, from, options.top.unwrap_or(1))?;
+    match metric {
+        Metric::Delay(_) => println!("Crossings by {metric}:"),
+        _ => println!("Crossings by {metric} from {},{}:", from.x, from.y),
+    }
+    for (rank, crossing) in crossings.iter().enumerate() {
+        println!(
+            "{:>4}. {},{}: {}",
+            rank + 1,
+            crossing.point.x,
+            crossing.point.y,
+            crossing.value
+        );
+    }
+    Ok(true)
+}
@@ -0,0 +1,87 @@
+use std::fmt;
+
+/*
+The eight ways a wire can run. The puzzle only uses the first four, other
+tools write them as compass points or go diagonally, see `SPELLINGS`.
+*/
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+use Direction::*;
+
+/// Clockwise from `Up`, an eighth of a turn apart.
+const CLOCKWISE: [Direction; 8] = [Up, UpRight, Right, DownRight, Down, DownLeft, Left, UpLeft];
+
+/// How directions are written: `U`, `D`, `L`, `R` as in the puzzle, compass
+/// points with north up, and the diagonals in between.
+pub const SPELLINGS: &[(&str, Direction)] = &[
+    ("U", Up),
+    ("D", Down),
+    ("L", Left),
+    ("R", Right),
+    ("N", Up),
+    ("S", Down),
+    ("W", Left),
+    ("E", Right),
+    ("NE", UpRight),
+    ("NW", UpLeft),
+    ("SE", DownRight),
+    ("SW", DownLeft),
+];
+
+impl Direction {
+    /// How `x` and `y` change with every step, `y` grows upwards.
+    pub fn delta(self) -> (i32, i32) {
+        match self {
+            Up => (0, 1),
+            UpRight => (1, 1),
+            Right => (1, 0),
+            DownRight => (1, -1),
+            Down => (0, -1),
+            DownLeft => (-1, -1),
+            Left => (-1, 0),
+            UpLeft => (-1, 1),
+        }
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        let (dx, dy) = self.delta();
+        dx != 0 && dy != 0
+    }
+
+    /// The direction after turning `eighths` eighths of a full turn
+    /// clockwise, counterclockwise if negative.
+    pub fn turned(self, eighths: i32) -> Self {
+        let index = CLOCKWISE
+            .iter()
+            .position(|&direction| direction == self)
+            .expect("every direction is in CLOCKWISE");
+        CLOCKWISE[(index as i32 + eighths).rem_euclid(8) as usize]
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let arrow = match self {
+            Up => "⬆️",
+            UpRight => "↗️",
+            Right => "➡️",
+            DownRight => "↘️",
+            Down => "⬇️",
+            DownLeft => "↙️",
+            Left => "⬅️",
+            UpLeft => "↖️",
+        };
+        write!(f, "{arrow}")
+    }
+}
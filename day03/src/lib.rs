@@ -0,0 +1,95 @@
+use anyhow::{anyhow, Result};
+use aoc_common::{Answer, Solution, Value};
+use std::fmt;
+
+/*
+https://adventofcode.com/2019/day/03
+
+Grid coordinates shared with the other days (e.g. the day11 hull robot).
+
+Y grows upwards, so `U` moves to `y + 1` and `D` to `y - 1`.
+*/
+
+pub mod cli;
+pub mod direction;
+pub mod metric;
+pub mod render;
+pub mod segment;
+pub mod wire;
+pub mod wire_set;
+
+use wire::{parse_wires, Delay, Instruction};
+use wire_set::WireSet;
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+/// The puzzle asks about the first two wires, inputs can have more.
+pub const PUZZLE_WIRES: [usize; 2] = [0, 1];
+
+impl Point {
+    pub const fn new(x: i32, y: i32) -> Self {
+        Point { x, y }
+    }
+}
+
+/// The best crossing of the wires by some measure (distance, steps), the
+/// answer is the measure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Crossing<T> {
+    pub value: T,
+    pub point: Point,
+}
+
+impl<T: fmt::Display> fmt::Display for Crossing<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl<T: fmt::Display> Answer for Crossing<T> {
+    fn diagnostics(&self) -> Vec<(&'static str, Value)> {
+        let point = vec![self.point.x.into(), self.point.y.into()];
+        vec![("crossing", Value::Numbers(point))]
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    type Input = Vec<Vec<Instruction>>;
+    type Answer1 = Crossing<u64>;
+    type Answer2 = Crossing<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse_wires(input)?)
+    }
+
+    /*
+    Trace the wires as segments
+        Sweep for the cells the first two share
+        Return the smallest Manhattan Distance
+    */
+    fn part1(wires: &Self::Input) -> Result<Crossing<u64>> {
+        WireSet::trace(wires)?
+            .closest_crossing(&PUZZLE_WIRES)?
+            .ok_or_else(|| anyhow!("The wires never cross"))
+    }
+
+    /*
+    Fewest combined steps to reach an intersection
+        Segments know the steps to their start, add the rest of the way
+     */
+    fn part2(wires: &Self::Input) -> Result<Crossing<usize>> {
+        WireSet::trace(wires)?
+            .fewest_combined_steps(&PUZZLE_WIRES, Delay::First)?
+            .ok_or_else(|| anyhow!("The wires never cross"))
+    }
+}
@@ -0,0 +1,96 @@
+use crate::wire::Delay;
+use crate::Point;
+use anyhow::{bail, Result};
+use std::fmt;
+use std::str::FromStr;
+
+/*
+How crossings are ranked: by how far they are from a reference point (the
+central port in the puzzle) or by the signal delay to them, which is always
+counted from the port.
+*/
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// |dx| + |dy|, the puzzle's.
+    Manhattan,
+    /// max(|dx|, |dy|)
+    Chebyshev,
+    /// √(dx² + dy²)
+    Euclidean,
+    /// Combined steps of the wires, see `Delay`.
+    Delay(Delay),
+}
+
+/// A crossing's rank by some `Metric`, lower is better. Scores of
+/// different metrics don't compare.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Score {
+    Distance(u64),
+    /// Squared, so that it compares exactly.
+    EuclideanSquared(u128),
+    Delay(usize),
+}
+
+impl Metric {
+    /// How far `point` is from `reference`, `None` for `Delay`, which
+    /// depends on the wires.
+    pub fn distance(&self, point: Point, reference: Point) -> Option<Score> {
+        let dx = u64::from(point.x.abs_diff(reference.x));
+        let dy = u64::from(point.y.abs_diff(reference.y));
+        match self {
+            Metric::Manhattan => Some(Score::Distance(dx + dy)),
+            Metric::Chebyshev => Some(Score::Distance(dx.max(dy))),
+            Metric::Euclidean => {
+                let (dx, dy) = (u128::from(dx), u128::from(dy));
+                Some(Score::EuclideanSquared(dx * dx + dy * dy))
+            }
+            Metric::Delay(_) => None,
+        }
+    }
+}
+
+impl FromStr for Metric {
+    type Err = anyhow::Error;
+
+    /// The delay is counted to the first visits, `Metric::Delay` takes any.
+    fn from_str(metric: &str) -> Result<Self> {
+        match metric {
+            "manhattan" => Ok(Metric::Manhattan),
+            "chebyshev" => Ok(Metric::Chebyshev),
+            "euclidean" => Ok(Metric::Euclidean),
+            "delay" => Ok(Metric::Delay(Delay::First)),
+            _ => bail!(
+                "Unknown metric {metric:?}, expected manhattan, chebyshev, euclidean or delay"
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Metric::Manhattan => write!(f, "Manhattan distance"),
+            Metric::Chebyshev => write!(f, "Chebyshev distance"),
+            Metric::Euclidean => write!(f, "Euclidean distance"),
+            Metric::Delay(delay) => write!(f, "{delay} delay"),
+        }
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Score::Distance(distance) => write!(f, "{distance}"),
+            Score::EuclideanSquared(squared) => {
+                let distance = (*squared as f64).sqrt();
+                if distance.fract() == 0.0 {
+                    write!(f, "{distance}")
+                } else {
+                    write!(f, "{distance:.3}")
+                }
+            }
+            Score::Delay(steps) => write!(f, "{steps}"),
+        }
+    }
+}
@@ -0,0 +1,404 @@
+use crate::segment::{Orientation, Overlap};
+use crate::wire::Delay;
+use crate::wire_set::WireSet;
+use crate::{Crossing, Point, ORIGIN};
+use anyhow::Result;
+use std::fmt::Write;
+
+/*
+Pictures of the wires: every wire from the central port in its own color,
+the cells wires share marked, and (when there are wires to compare) the
+closest and lowest-delay crossing of the compared wires singled out.
+
+All outputs scale to fit, wires millions of steps long come out the same
+size as the examples. SVG and PNG share a layout, the PNG has no legend
+though. The terminal preview lumps neighbouring cells into one character
+once the wires get too big for it, so it's best for small inputs.
+*/
+
+/// Longest side of an SVG or PNG picture in pixels, without the margin.
+pub const SVG_SIZE: f64 = 800.0;
+const SVG_MARGIN: f64 = 40.0;
+
+const WHITE: [u8; 3] = [255, 255, 255];
+const BLACK: [u8; 3] = [0, 0, 0];
+const GRAY: [u8; 3] = [150, 150, 150];
+
+/// Terminal colors for the wires, in turn.
+const ANSI_COLORS: [u8; 6] = [31, 32, 34, 35, 36, 33];
+
+pub struct Picture<'a> {
+    set: &'a WireSet,
+    crossings: Vec<Overlap>,
+    closest: Option<Crossing<u64>>,
+    fastest: Option<Crossing<usize>>,
+}
+
+/// The smallest and largest `x` and `y` of the port and all wires.
+fn bounds(set: &WireSet) -> (Point, Point) {
+    let (mut min, mut max) = (ORIGIN, ORIGIN);
+    for wire in 0..set.len() {
+        for segment in set.wire(wire).segments() {
+            for point in [segment.start, segment.end] {
+                min = Point::new(min.x.min(point.x), min.y.min(point.y));
+                max = Point::new(max.x.max(point.x), max.y.max(point.y));
+            }
+        }
+    }
+    (min, max)
+}
+
+/// Far apart hues, however many wires there are.
+fn hue(wire: usize) -> f64 {
+    (wire as f64 * 137.508) % 360.0
+}
+
+fn svg_color(wire: usize) -> String {
+    format!("hsl({:.0}, 75%, 40%)", hue(wire))
+}
+
+/// `svg_color` as RGB.
+fn rgb_color(wire: usize) -> [u8; 3] {
+    let (saturation, lightness) = (0.75, 0.4);
+    let chroma = (1.0 - f64::abs(2.0 * lightness - 1.0)) * saturation;
+    let sector = hue(wire) / 60.0;
+    let second = chroma * (1.0 - f64::abs(sector % 2.0 - 1.0));
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, second, 0.0),
+        1 => (second, chroma, 0.0),
+        2 => (0.0, chroma, second),
+        3 => (0.0, second, chroma),
+        4 => (second, 0.0, chroma),
+        _ => (chroma, 0.0, second),
+    };
+    let base = lightness - chroma / 2.0;
+    [r, g, b].map(|channel| ((channel + base) * 255.0).round() as u8)
+}
+
+/// Where the wires go on an SVG or PNG picture.
+struct Frame {
+    min: Point,
+    max: Point,
+    scale: f64,
+}
+
+impl Frame {
+    fn new(set: &WireSet) -> Self {
+        let (min, max) = bounds(set);
+        let range = (f64::from(max.x) - f64::from(min.x)).max(f64::from(max.y) - f64::from(min.y));
+        let scale = if range > 0.0 { SVG_SIZE / range } else { 1.0 };
+        Frame { min, max, scale }
+    }
+
+    /// Width and height in pixels.
+    fn size(&self) -> (f64, f64) {
+        (
+            (f64::from(self.max.x) - f64::from(self.min.x)) * self.scale + 2.0 * SVG_MARGIN,
+            (f64::from(self.max.y) - f64::from(self.min.y)) * self.scale + 2.0 * SVG_MARGIN,
+        )
+    }
+
+    /// `y` grows upwards, in pictures downwards.
+    fn at(&self, point: Point) -> (f64, f64) {
+        (
+            (f64::from(point.x) - f64::from(self.min.x)) * self.scale + SVG_MARGIN,
+            (f64::from(self.max.y) - f64::from(point.y)) * self.scale + SVG_MARGIN,
+        )
+    }
+}
+
+/// RGB pixels to draw the PNG on.
+struct Canvas {
+    width: usize,
+    height: usize,
+    data: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize) -> Self {
+        Canvas {
+            width,
+            height,
+            data: WHITE.repeat(width * height),
+        }
+    }
+
+    fn plot(&mut self, x: f64, y: f64, color: [u8; 3]) {
+        let (x, y) = (x.round(), y.round());
+        if x < 0.0 || y < 0.0 || x >= self.width as f64 || y >= self.height as f64 {
+            return;
+        }
+        let start = (y as usize * self.width + x as usize) * 3;
+        self.data[start..start + 3].copy_from_slice(&color);
+    }
+
+    /// A line `pen` pixels wide.
+    fn line(&mut self, from: (f64, f64), to: (f64, f64), pen: usize, color: [u8; 3]) {
+        let steps = (to.0 - from.0)
+            .abs()
+            .max((to.1 - from.1).abs())
+            .ceil()
+            .max(1.0);
+        let offset = (pen as f64 - 1.0) / 2.0;
+        for i in 0..=steps as usize {
+            let t = i as f64 / steps;
+            let (x, y) = (from.0 + (to.0 - from.0) * t, from.1 + (to.1 - from.1) * t);
+            for dx in 0..pen {
+                for dy in 0..pen {
+                    self.plot(x + dx as f64 - offset, y + dy as f64 - offset, color);
+                }
+            }
+        }
+    }
+
+    fn disc(&mut self, (x, y): (f64, f64), radius: f64, color: [u8; 3]) {
+        let reach = radius.ceil() as i64;
+        for dx in -reach..=reach {
+            for dy in -reach..=reach {
+                if ((dx * dx + dy * dy) as f64) <= radius * radius {
+                    self.plot(x + dx as f64, y + dy as f64, color);
+                }
+            }
+        }
+    }
+
+    /// A circle two pixels wide, broken into dashes if `dashed`.
+    fn ring(&mut self, (x, y): (f64, f64), radius: f64, dashed: bool, color: [u8; 3]) {
+        let steps = (radius * 16.0).ceil() as usize;
+        for i in 0..steps {
+            if dashed && (i * 16 / steps) % 2 == 1 {
+                continue;
+            }
+            let angle = i as f64 / steps as f64 * std::f64::consts::TAU;
+            for r in [radius - 0.5, radius + 0.5] {
+                self.plot(x + r * angle.cos(), y + r * angle.sin(), color);
+            }
+        }
+    }
+}
+
+impl<'a> Picture<'a> {
+    /// Draws all wires in `set` and the cells they share, without
+    /// highlights, so a single wire can be drawn too.
+    pub fn new(set: &'a WireSet) -> Self {
+        Picture {
+            set,
+            crossings: set.pairwise_crossings(),
+            closest: None,
+            fastest: None,
+        }
+    }
+
+    /// Highlights the closest and lowest-delay crossing of `wires` (at
+    /// least two of them).
+    pub fn highlight(mut self, wires: &[usize], delay: Delay) -> Result<Self> {
+        self.closest = self.set.closest_crossing(wires)?;
+        self.fastest = self.set.fewest_combined_steps(wires, delay)?;
+        Ok(self)
+    }
+
+    pub fn to_svg(&self) -> String {
+        let frame = Frame::new(self.set);
+        let (width, height) = frame.size();
+        let at = |point: Point| frame.at(point);
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width:.0}" height="{height:.0}" viewBox="0 0 {width:.2} {height:.2}">"#
+        );
+        let _ = writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#);
+        for wire in 0..self.set.len() {
+            let segments = self.set.wire(wire).segments();
+            let points: Vec<String> = std::iter::once(ORIGIN)
+                .chain(segments.iter().map(|segment| segment.end))
+                .map(|point| {
+                    let (x, y) = at(point);
+                    format!("{x:.2},{y:.2}")
+                })
+                .collect();
+            let _ = writeln!(
+                svg,
+                r#"<polyline fill="none" stroke="{}" stroke-width="2" points="{}"><title>wire {wire}</title></polyline>"#,
+                svg_color(wire),
+                points.join(" ")
+            );
+        }
+        for overlap in &self.crossings {
+            let ((x1, y1), (x2, y2)) = (at(overlap.from), at(overlap.to));
+            if overlap.len() == 1 {
+                let _ = writeln!(
+                    svg,
+                    r#"<circle cx="{x1:.2}" cy="{y1:.2}" r="3" fill="black"/>"#
+                );
+            } else {
+                let _ = writeln!(
+                    svg,
+                    r#"<line x1="{x1:.2}" y1="{y1:.2}" x2="{x2:.2}" y2="{y2:.2}" stroke="black" stroke-width="5" stroke-opacity="0.4"/>"#
+                );
+            }
+        }
+        let (x, y) = at(ORIGIN);
+        let _ = writeln!(
+            svg,
+            r#"<circle cx="{x:.2}" cy="{y:.2}" r="5" fill="white" stroke="black" stroke-width="2"><title>central port</title></circle>"#
+        );
+        let mut legend = Vec::new();
+        if let Some(closest) = self.closest {
+            let (x, y) = at(closest.point);
+            let label = format!(
+                "closest: {} at {},{}",
+                closest.value, closest.point.x, closest.point.y
+            );
+            let _ = writeln!(
+                svg,
+                r#"<circle cx="{x:.2}" cy="{y:.2}" r="9" fill="none" stroke="black" stroke-width="2"><title>{label}</title></circle>"#
+            );
+            legend.push(("black".to_string(), format!("(solid ring) {label}")));
+        }
+        if let Some(fastest) = self.fastest {
+            let (x, y) = at(fastest.point);
+            let label = format!(
+                "lowest delay: {} at {},{}",
+                fastest.value, fastest.point.x, fastest.point.y
+            );
+            let _ = writeln!(
+                svg,
+                r#"<circle cx="{x:.2}" cy="{y:.2}" r="14" fill="none" stroke="black" stroke-width="2" stroke-dasharray="4 3"><title>{label}</title></circle>"#
+            );
+            legend.push(("black".to_string(), format!("(dashed ring) {label}")));
+        }
+        legend.extend((0..self.set.len()).map(|wire| (svg_color(wire), format!("wire {wire}"))));
+        for (line, (color, label)) in legend.iter().enumerate() {
+            let _ = writeln!(
+                svg,
+                r#"<text x="8" y="{}" font-family="monospace" font-size="12" fill="{color}">{label}</text>"#,
+                14 + 14 * line
+            );
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// The SVG picture as a PNG image, without the legend.
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        let frame = Frame::new(self.set);
+        let (width, height) = frame.size();
+        let mut canvas = Canvas::new(width.ceil() as usize, height.ceil() as usize);
+        for wire in 0..self.set.len() {
+            let mut from = frame.at(ORIGIN);
+            for segment in self.set.wire(wire).segments() {
+                let to = frame.at(segment.end);
+                canvas.line(from, to, 2, rgb_color(wire));
+                from = to;
+            }
+        }
+        for overlap in &self.crossings {
+            if overlap.len() == 1 {
+                canvas.disc(frame.at(overlap.from), 3.0, BLACK);
+            } else {
+                canvas.line(frame.at(overlap.from), frame.at(overlap.to), 5, GRAY);
+            }
+        }
+        canvas.disc(frame.at(ORIGIN), 5.0, WHITE);
+        canvas.ring(frame.at(ORIGIN), 5.0, false, BLACK);
+        if let Some(closest) = self.closest {
+            canvas.ring(frame.at(closest.point), 9.0, false, BLACK);
+        }
+        if let Some(fastest) = self.fastest {
+            canvas.ring(frame.at(fastest.point), 14.0, true, BLACK);
+        }
+
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, canvas.width as u32, canvas.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(&canvas.data)?;
+        Ok(png)
+    }
+
+    /// At most `width` x `height` characters, `-` and `|` for the wires, `+`
+    /// where they turn, `X` where they cross, `o` for the central port, `*`
+    /// for the closest crossing and `@` for the one with the lowest delay.
+    /// Wires are colored with ANSI escapes if `color`.
+    pub fn to_terminal(&self, width: usize, height: usize, color: bool) -> String {
+        let (min, max) = bounds(self.set);
+        let span = |low: i32, high: i32| u64::from(low.abs_diff(high)) + 1;
+        // Cells per character, the same both ways.
+        let cells = span(min.x, max.x)
+            .div_ceil(width.max(1) as u64)
+            .max(span(min.y, max.y).div_ceil(height.max(1) as u64))
+            .max(1);
+        let column = |x: i32| (u64::from(min.x.abs_diff(x)) / cells) as usize;
+        let row = |y: i32| (u64::from(max.y.abs_diff(y)) / cells) as usize;
+        let (columns, rows) = (column(max.x) + 1, row(min.y) + 1);
+
+        let mut grid: Vec<Vec<(char, Option<usize>)>> = vec![vec![(' ', None); columns]; rows];
+        for wire in 0..self.set.len() {
+            for segment in self.set.wire(wire).segments() {
+                let (c0, r0) = (column(segment.start.x), row(segment.start.y));
+                let (c1, r1) = (column(segment.end.x), row(segment.end.y));
+                let line = match segment.orientation() {
+                    Orientation::Horizontal => '-',
+                    Orientation::Vertical => '|',
+                    Orientation::Diagonal => '/',
+                    Orientation::AntiDiagonal => '\\',
+                };
+                let (dc, dr) = (c1 as i64 - c0 as i64, r1 as i64 - r0 as i64);
+                let length = dc.abs().max(dr.abs()).max(1);
+                for i in 0..=length {
+                    let c = (c0 as i64 + dc * i / length) as usize;
+                    let r = (r0 as i64 + dr * i / length) as usize;
+                    let cell = &mut grid[r][c];
+                    cell.0 = match cell.0 {
+                        ' ' => line,
+                        drawn if drawn == line => line,
+                        _ => '+',
+                    };
+                    cell.1 = Some(wire);
+                }
+            }
+        }
+        let mut mark =
+            |point: Point, mark: char| grid[row(point.y)][column(point.x)] = (mark, None);
+        for overlap in &self.crossings {
+            for point in [overlap.from, overlap.to] {
+                mark(point, 'X');
+            }
+        }
+        mark(ORIGIN, 'o');
+        if let Some(fastest) = self.fastest {
+            mark(fastest.point, '@');
+        }
+        if let Some(closest) = self.closest {
+            mark(closest.point, '*');
+        }
+
+        let mut text = String::new();
+        for line in &grid {
+            let mut current = None;
+            for &(c, wire) in line {
+                if color && wire != current {
+                    match wire {
+                        Some(wire) => {
+                            let _ = write!(text, "\x1b[{}m", ANSI_COLORS[wire % ANSI_COLORS.len()]);
+                        }
+                        None => text.push_str("\x1b[0m"),
+                    }
+                    current = wire;
+                }
+                text.push(c);
+            }
+            if color && current.is_some() {
+                text.push_str("\x1b[0m");
+            }
+            let trimmed = text.trim_end_matches(' ').len();
+            text.truncate(trimmed);
+            text.push('\n');
+        }
+        if cells > 1 {
+            let _ = writeln!(text, "(1 character = {cells}x{cells} cells)");
+        }
+        text
+    }
+}
@@ -0,0 +1,435 @@
+use crate::wire::{calculate_manhattan_distance, Instruction};
+use crate::{Point, ORIGIN};
+use anyhow::{anyhow, Result};
+use std::collections::{BTreeMap, HashMap};
+
+/*
+Wires as straight segments instead of one point per step, so moves of any
+length take the same memory. Segments run along one of four orientations,
+diagonal ones move a cell up or down with every cell sideways, and every
+cell on the way is a step.
+
+A segment covers the cells after its `start` up to and including its `end`,
+`start` is the end of the segment before (or the central port, which the
+wire only visits once it comes back). Where segments of different wires
+share cells is found by sweeping a vertical line from left to right:
+horizontal segments are active while the line is over them, every vertical
+segment it reaches crosses the active ones in its `y` range. Segments on
+the same line can also run along each other, those are found by sweeping
+along every line. Diagonal segments are checked against every segment they
+overlap from left to right, in a third sweep.
+*/
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    Horizontal,
+    Vertical,
+    /// Up to the right, like `/`.
+    Diagonal,
+    /// Down to the right, like `\`.
+    AntiDiagonal,
+}
+
+impl Orientation {
+    pub const ALL: [Orientation; 4] = [
+        Orientation::Horizontal,
+        Orientation::Vertical,
+        Orientation::Diagonal,
+        Orientation::AntiDiagonal,
+    ];
+
+    /// The line through `point` in this orientation, numbered by where it
+    /// meets an axis, and how far along it `point` is: `x`, or `y` on
+    /// vertical lines.
+    fn locate(self, point: Point) -> (i64, i32) {
+        let (x, y) = (i64::from(point.x), i64::from(point.y));
+        match self {
+            Orientation::Horizontal => (y, point.x),
+            Orientation::Vertical => (x, point.y),
+            Orientation::Diagonal => (y - x, point.x),
+            Orientation::AntiDiagonal => (y + x, point.x),
+        }
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        matches!(self, Orientation::Diagonal | Orientation::AntiDiagonal)
+    }
+
+    /// The line in this orientation through `point`, as `Segment::line`
+    /// numbers it.
+    pub fn line_through(self, point: Point) -> (Orientation, i64) {
+        (self, self.locate(point).0)
+    }
+
+    /// The point `along` line `line`, the reverse of `locate`.
+    fn point(self, line: i64, along: i32) -> Point {
+        let on = |y: i64| Point::new(along, y as i32);
+        match self {
+            Orientation::Horizontal => on(line),
+            Orientation::Vertical => Point::new(line as i32, along),
+            Orientation::Diagonal => on(line + i64::from(along)),
+            Orientation::AntiDiagonal => on(line - i64::from(along)),
+        }
+    }
+
+    /// The line as `a * x + b * y = line`.
+    fn equation(self) -> (i64, i64) {
+        match self {
+            Orientation::Horizontal => (0, 1),
+            Orientation::Vertical => (1, 0),
+            Orientation::Diagonal => (-1, 1),
+            Orientation::AntiDiagonal => (1, 1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+    /// Steps along the wire to `start`.
+    pub steps: usize,
+}
+
+/// The cells of a segment: the line it's on and the range it covers along
+/// it, see `Orientation::locate`.
+#[derive(Debug, Clone, Copy)]
+struct Span {
+    line: i64,
+    low: i32,
+    high: i32,
+}
+
+/// The cell `i` steps from `from` in a straight line towards `to`.
+pub(crate) fn towards(from: Point, to: Point, i: i64) -> Point {
+    let along = |from: i32, to: i32| i64::from(from) + i * to.cmp(&from) as i64;
+    Point::new(along(from.x, to.x) as i32, along(from.y, to.y) as i32)
+}
+
+/// Steps between two cells on the same segment.
+pub(crate) fn distance(a: Point, b: Point) -> usize {
+    a.x.abs_diff(b.x).max(a.y.abs_diff(b.y)) as usize
+}
+
+impl Segment {
+    pub fn orientation(&self) -> Orientation {
+        match (self.start.x == self.end.x, self.start.y == self.end.y) {
+            (_, true) => Orientation::Horizontal,
+            (true, false) => Orientation::Vertical,
+            _ if (self.end.x > self.start.x) == (self.end.y > self.start.y) => {
+                Orientation::Diagonal
+            }
+            _ => Orientation::AntiDiagonal,
+        }
+    }
+
+    pub fn is_horizontal(&self) -> bool {
+        self.orientation() == Orientation::Horizontal
+    }
+
+    /// The line the segment is on, see `Orientation::locate`.
+    pub fn line(&self) -> (Orientation, i64) {
+        let orientation = self.orientation();
+        (orientation, orientation.locate(self.start).0)
+    }
+
+    pub fn len(&self) -> usize {
+        distance(self.start, self.end)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        let span = self.span();
+        let (line, along) = self.orientation().locate(point);
+        line == span.line && (span.low..=span.high).contains(&along)
+    }
+
+    /// Steps along the wire to `point`, which is on the segment.
+    pub fn steps_to(&self, point: Point) -> usize {
+        self.steps + distance(self.start, point)
+    }
+
+    /// The cell the wire is on after `steps`, which are on the segment.
+    pub fn at(&self, steps: usize) -> Point {
+        towards(self.start, self.end, (steps - self.steps) as i64)
+    }
+
+    fn span(&self) -> Span {
+        let orientation = self.orientation();
+        let (line, from) = orientation.locate(self.start);
+        let (_, to) = orientation.locate(self.end);
+        // `start` is not part of the segment.
+        let (low, high) = if from < to {
+            (from + 1, to)
+        } else {
+            (to, from - 1)
+        };
+        Span { line, low, high }
+    }
+
+    /// The lowest and highest `x` of the segment's cells.
+    fn columns(&self) -> (i32, i32) {
+        match self.orientation() {
+            Orientation::Vertical => (self.start.x, self.start.x),
+            _ => {
+                let span = self.span();
+                (span.low, span.high)
+            }
+        }
+    }
+
+    fn point(&self, line: i64, along: i32) -> Point {
+        self.orientation().point(line, along)
+    }
+}
+
+/// Follows a wire from the central port, one segment per move. Moves of
+/// zero steps leave no segment.
+pub fn trace_segments(instructions: &[Instruction]) -> Result<Vec<Segment>> {
+    let mut segments = Vec::with_capacity(instructions.len());
+    let mut position = ORIGIN;
+    let mut steps: usize = 0;
+    for instruction in instructions {
+        let (dx, dy) = instruction.direction.delta();
+        let end = (|| {
+            Some(Point::new(
+                position.x.checked_add(dx * instruction.steps)?,
+                position.y.checked_add(dy * instruction.steps)?,
+            ))
+        })()
+        .ok_or_else(|| anyhow!("The wire leaves the grid after {steps} steps"))?;
+        if instruction.steps > 0 {
+            segments.push(Segment {
+                start: position,
+                end,
+                steps,
+            });
+        }
+        steps += instruction.steps as usize;
+        position = end;
+    }
+    Ok(segments)
+}
+
+/// Cells two wires share: a single cell where they cross, or a stretch
+/// where they run along each other, from `from` to `to`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overlap {
+    /// Indices of the two wires, the first one is lower.
+    pub wires: (usize, usize),
+    pub segments: (Segment, Segment),
+    pub from: Point,
+    pub to: Point,
+}
+
+impl Overlap {
+    pub fn len(&self) -> usize {
+        distance(self.from, self.to) + 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Every shared cell, from `from` to `to`.
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.len() as i64).map(move |i| self.cell(i))
+    }
+
+    /// The `i`th shared cell, counted from `from`.
+    fn cell(&self, i: i64) -> Point {
+        towards(self.from, self.to, i)
+    }
+
+    /// The shared cell closest to the central port, and its distance.
+    pub fn closest(&self) -> (u64, Point) {
+        // Along a straight stretch the distance only turns where it
+        // crosses the port's row or column, one of these cells or the
+        // ends is closest.
+        let last = self.len() as i64 - 1;
+        let turns = [(self.from.x, self.to.x), (self.from.y, self.to.y)]
+            .into_iter()
+            .filter(|(from, to)| from != to)
+            .map(|(from, to)| {
+                let step = to.cmp(&from) as i64;
+                self.cell((-i64::from(from) * step).clamp(0, last))
+            });
+        [self.from, self.to]
+            .into_iter()
+            .chain(turns)
+            .map(|point| (calculate_manhattan_distance(&point), point))
+            .min_by_key(|&(distance, point)| (distance, point.x, point.y))
+            .expect("the ends are candidates")
+    }
+
+    /// The shared cell both wires reach in the fewest combined steps, and
+    /// those steps.
+    pub fn fewest_steps(&self) -> (usize, Point) {
+        // Combined steps change evenly along the stretch, they are lowest
+        // at one of its ends.
+        [self.from, self.to]
+            .into_iter()
+            .map(|point| {
+                let (a, b) = self.segments;
+                (a.steps_to(point) + b.steps_to(point), point)
+            })
+            .min_by_key(|&(steps, point)| (steps, point.x, point.y))
+            .expect("two candidates")
+    }
+}
+
+/// Where segments of different wires share cells, as found by the sweep.
+pub fn find_crossings<W: AsRef<[Segment]>>(wires: &[W]) -> Vec<Overlap> {
+    let segments: Vec<(usize, Segment)> = wires
+        .iter()
+        .enumerate()
+        .flat_map(|(wire, segments)| {
+            segments
+                .as_ref()
+                .iter()
+                .map(move |&segment| (wire, segment))
+        })
+        .collect();
+    let mut overlaps = Vec::new();
+    sweep_perpendicular(&segments, &mut overlaps);
+    sweep_along_lines(&segments, &mut overlaps);
+    sweep_diagonals(&segments, &mut overlaps);
+    overlaps
+}
+
+fn overlap(a: (usize, Segment), b: (usize, Segment), from: Point, to: Point) -> Overlap {
+    let (a, b) = if a.0 <= b.0 { (a, b) } else { (b, a) };
+    Overlap {
+        wires: (a.0, b.0),
+        segments: (a.1, b.1),
+        from,
+        to,
+    }
+}
+
+/// Horizontal segments crossing vertical ones.
+fn sweep_perpendicular(segments: &[(usize, Segment)], overlaps: &mut Vec<Overlap>) {
+    // At the same `x` horizontal segments start before vertical ones are
+    // checked against them, and end after.
+    const START: u8 = 0;
+    const CHECK: u8 = 1;
+    const END: u8 = 2;
+    let mut events: Vec<(i32, u8, usize)> = Vec::new();
+    for (index, (_, segment)) in segments.iter().enumerate() {
+        let span = segment.span();
+        match segment.orientation() {
+            Orientation::Horizontal => {
+                events.push((span.low, START, index));
+                events.push((span.high, END, index));
+            }
+            Orientation::Vertical => events.push((span.line as i32, CHECK, index)),
+            _ => {}
+        }
+    }
+    events.sort_unstable();
+
+    // Active horizontal segments by their `y`.
+    let mut active: BTreeMap<i64, Vec<usize>> = BTreeMap::new();
+    for (x, kind, index) in events {
+        let (wire, segment) = segments[index];
+        let span = segment.span();
+        match kind {
+            START => active.entry(span.line).or_default().push(index),
+            END => {
+                let row = active.get_mut(&span.line).expect("started before");
+                row.retain(|&other| other != index);
+                if row.is_empty() {
+                    active.remove(&span.line);
+                }
+            }
+            _ => {
+                for (&y, row) in active.range(i64::from(span.low)..=i64::from(span.high)) {
+                    for &other in row {
+                        if segments[other].0 != wire {
+                            let point = Point::new(x, y as i32);
+                            overlaps.push(overlap(segments[other], (wire, segment), point, point));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Segments running along each other on the same line.
+fn sweep_along_lines(segments: &[(usize, Segment)], overlaps: &mut Vec<Overlap>) {
+    let mut lines: HashMap<(Orientation, i64), Vec<(Span, usize)>> = HashMap::new();
+    for (index, (_, segment)) in segments.iter().enumerate() {
+        let span = segment.span();
+        lines.entry(segment.line()).or_default().push((span, index));
+    }
+    for mut line in lines.into_values() {
+        line.sort_unstable_by_key(|(span, _)| span.low);
+        let mut active: Vec<(Span, usize)> = Vec::new();
+        for (span, index) in line {
+            active.retain(|(other, _)| other.high >= span.low);
+            let (wire, segment) = segments[index];
+            for &(other, other_index) in &active {
+                if segments[other_index].0 != wire {
+                    let high = span.high.min(other.high);
+                    let from = segment.point(span.line, span.low);
+                    let to = segment.point(span.line, high);
+                    overlaps.push(overlap(segments[other_index], (wire, segment), from, to));
+                }
+            }
+            active.push((span, index));
+        }
+    }
+}
+
+/// The cell where two segments on lines of different orientations meet, if
+/// they both cover it.
+fn intersection(a: &Segment, b: &Segment) -> Option<Point> {
+    let ((oa, ca), (ob, cb)) = (a.line(), b.line());
+    let ((a1, b1), (a2, b2)) = (oa.equation(), ob.equation());
+    let det = a1 * b2 - a2 * b1;
+    // Crossing diagonals can meet between cells.
+    if det == 0 || (ca * b2 - cb * b1) % det != 0 || (a1 * cb - a2 * ca) % det != 0 {
+        return None;
+    }
+    let x = i32::try_from((ca * b2 - cb * b1) / det).ok()?;
+    let y = i32::try_from((a1 * cb - a2 * ca) / det).ok()?;
+    let point = Point::new(x, y);
+    (a.contains(point) && b.contains(point)).then_some(point)
+}
+
+/// Diagonal segments crossing segments of other orientations: everything
+/// that overlaps them from left to right is checked.
+fn sweep_diagonals(segments: &[(usize, Segment)], overlaps: &mut Vec<Overlap>) {
+    let mut columns: Vec<((i32, i32), usize)> = segments
+        .iter()
+        .enumerate()
+        .map(|(index, (_, segment))| (segment.columns(), index))
+        .collect();
+    let is_diagonal = |segment: &Segment| segment.orientation().is_diagonal();
+    if !segments.iter().any(|(_, segment)| is_diagonal(segment)) {
+        return;
+    }
+    columns.sort_unstable();
+    let mut active: Vec<((i32, i32), usize)> = Vec::new();
+    for ((low, high), index) in columns {
+        active.retain(|&((_, other_high), _)| other_high >= low);
+        let (wire, segment) = segments[index];
+        for &(_, other_index) in &active {
+            let (other_wire, other) = segments[other_index];
+            if other_wire == wire
+                || !(is_diagonal(&segment) || is_diagonal(&other))
+                || segment.orientation() == other.orientation()
+            {
+                continue;
+            }
+            if let Some(point) = intersection(&segment, &other) {
+                overlaps.push(overlap((other_wire, other), (wire, segment), point, point));
+            }
+        }
+        active.push(((low, high), index));
+    }
+}
@@ -0,0 +1,507 @@
+use crate::direction::{Direction, SPELLINGS};
+use crate::segment::{find_crossings, trace_segments, Orientation, Overlap, Segment};
+use crate::{Point, ORIGIN};
+use anyhow::{bail, Result};
+use aoc_common::parse::{direction_count, from_str, lines_of, separated_by, Parser};
+use aoc_common::ParseError;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+/*
+A wire is the path it takes from the central port, stored as segments (see
+`segment.rs`). The wire starts at the port without visiting it: the port
+only counts as visited, and as a crossing, when the wire comes back to it.
+Every other cell is first visited after as many steps as it takes to get
+there, the cell next to the port after 1.
+
+A wire that comes back to a cell it was on before has made a loop. Which
+visit counts for the signal delay is up to the caller (see `Delay`).
+*/
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Instruction {
+    pub direction: Direction,
+    pub steps: i32,
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Direction: {}  Steps: {}", self.direction, self.steps)
+    }
+}
+
+fn steps() -> impl Parser<i32> {
+    from_str().try_map(|steps: i32| {
+        if steps < 0 {
+            return Err("step count must not be negative".to_string());
+        }
+        Ok(steps)
+    })
+}
+
+/// `R75` -> right, 75 steps. `NE3` -> up and right, 3 steps.
+fn instruction() -> impl Parser<Instruction> {
+    direction_count(SPELLINGS, steps()).map(|(direction, steps)| Instruction { direction, steps })
+}
+
+/// A move of a wire that steers like a turtle.
+enum Relative {
+    /// By eighths of a full turn, clockwise.
+    Turn(i32),
+    Forward(i32),
+}
+
+const TURN_LEFT: i32 = -1;
+const FORWARD: i32 = 0;
+const TURN_RIGHT: i32 = 1;
+
+/// `L90` turns left, `R45` right by as many degrees, `F10` moves on 10
+/// steps.
+fn relative() -> impl Parser<Relative> {
+    const MOVES: &[(&str, i32)] = &[("L", TURN_LEFT), ("R", TURN_RIGHT), ("F", FORWARD)];
+    direction_count(MOVES, from_str()).try_map(|(side, amount): (i32, i32)| {
+        if side == FORWARD {
+            if amount < 0 {
+                return Err("step count must not be negative".to_string());
+            }
+            return Ok(Relative::Forward(amount));
+        }
+        if amount % 45 != 0 {
+            return Err("turns must be multiples of 45 degrees".to_string());
+        }
+        Ok(Relative::Turn(side * amount / 45))
+    })
+}
+
+/// One wire. If any of its moves is `F<steps>` it steers like a turtle,
+/// starting upwards: `L` and `R` turn by degrees and `F` moves on.
+fn wire() -> impl Parser<Vec<Instruction>> {
+    |input: &str, line: &str| -> std::result::Result<Vec<Instruction>, ParseError> {
+        if !line
+            .split(',')
+            .any(|token| token.trim_start().starts_with('F'))
+        {
+            return separated_by(",", instruction()).parse_token(input, line);
+        }
+        let mut heading = Direction::Up;
+        Ok(separated_by(",", relative())
+            .parse_token(input, line)?
+            .into_iter()
+            .filter_map(|step| match step {
+                Relative::Turn(eighths) => {
+                    heading = heading.turned(eighths);
+                    None
+                }
+                Relative::Forward(steps) => Some(Instruction {
+                    direction: heading,
+                    steps,
+                }),
+            })
+            .collect())
+    }
+}
+
+/// A single instruction, the error explains what is wrong with it.
+pub fn parse_instruction(instruction: &str) -> std::result::Result<Instruction, String> {
+    self::instruction()
+        .parse(instruction)
+        .map_err(|err| err.reason)
+}
+
+pub fn calculate_manhattan_distance(point: &Point) -> u64 {
+    /*
+    Manhattan Distance
+        |x1 - x2| + |y1 - y2|
+    */
+
+    u64::from(ORIGIN.x.abs_diff(point.x)) + u64::from(ORIGIN.y.abs_diff(point.y))
+}
+
+/// One wire per line, its instructions separated by commas.
+pub fn parse_wires(contents: &str) -> std::result::Result<Vec<Vec<Instruction>>, ParseError> {
+    lines_of(wire()).parse(contents)
+}
+
+/// Which visit of a cell its signal delay is counted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delay {
+    /// The puzzle's: steps until the wire first gets there.
+    First,
+    /// Steps until the wire is there for the last time.
+    Last,
+    /// Steps along the wire with its loops cut out, a signal that can
+    /// jump where the wire touches itself.
+    LoopRemoved,
+}
+
+impl FromStr for Delay {
+    type Err = anyhow::Error;
+
+    fn from_str(delay: &str) -> Result<Self> {
+        match delay {
+            "first" => Ok(Delay::First),
+            "last" => Ok(Delay::Last),
+            "loop-removed" => Ok(Delay::LoopRemoved),
+            _ => bail!("Unknown delay {delay:?}, expected first, last or loop-removed"),
+        }
+    }
+}
+
+impl fmt::Display for Delay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Delay::First => write!(f, "first"),
+            Delay::Last => write!(f, "last"),
+            Delay::LoopRemoved => write!(f, "loop-removed"),
+        }
+    }
+}
+
+/// The wire leaves `point` after `from` steps and is back after `to`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Loop {
+    pub point: Point,
+    pub from: usize,
+    pub to: usize,
+}
+
+impl Loop {
+    pub fn len(&self) -> usize {
+        self.to - self.from
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Wire {
+    segments: Vec<Segment>,
+    /// Segment indices by the line they are on, see `Segment::line`.
+    lines: HashMap<(Orientation, i64), Vec<usize>>,
+    /// Where the wire shares cells with itself, see `self_crossings`.
+    self_overlaps: Vec<Overlap>,
+    /// Indices of the self-overlaps every segment is part of.
+    touching: Vec<Vec<usize>>,
+    /// The loop-removed delay after every step, in pieces that change by
+    /// the same amount with every step.
+    delays: Vec<Piece>,
+}
+
+/// Loop-removed delays from step `from` up to the next piece.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Piece {
+    from: usize,
+    delay: i64,
+    /// -1, 0 or 1.
+    slope: i64,
+}
+
+impl Piece {
+    fn at(&self, steps: usize) -> i64 {
+        self.delay + self.slope * (steps - self.from) as i64
+    }
+}
+
+/// Steps `from` to `to` revisit cells the wire was on after `back` steps
+/// at `from`, and then one step later or earlier with every step (`dir`).
+#[derive(Debug, Clone, Copy)]
+struct Shortcut {
+    from: usize,
+    to: usize,
+    back: usize,
+    dir: i64,
+}
+
+impl Shortcut {
+    fn back(&self, steps: usize) -> usize {
+        (self.back as i64 + self.dir * (steps - self.from) as i64) as usize
+    }
+}
+
+impl Wire {
+    pub fn trace(instructions: &[Instruction]) -> Result<Self> {
+        let segments = trace_segments(instructions)?;
+        let mut lines: HashMap<(Orientation, i64), Vec<usize>> = HashMap::new();
+        for (index, segment) in segments.iter().enumerate() {
+            lines.entry(segment.line()).or_default().push(index);
+        }
+        let mut wire = Wire {
+            segments,
+            lines,
+            self_overlaps: Vec::new(),
+            touching: Vec::new(),
+            delays: Vec::new(),
+        };
+        wire.self_overlaps = wire.self_crossings();
+        wire.touching = vec![Vec::new(); wire.segments.len()];
+        for (index, overlap) in wire.self_overlaps.iter().enumerate() {
+            wire.touching[overlap.wires.0].push(index);
+            wire.touching[overlap.wires.1].push(index);
+        }
+        wire.find_delays();
+        Ok(wire)
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Steps from the port to the end of the wire.
+    pub fn len(&self) -> usize {
+        self.segments
+            .last()
+            .map_or(0, |last| last.steps + last.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// The steps after which the wire is on `point`, in order.
+    pub fn visits(&self, point: Point) -> Vec<usize> {
+        let mut visits: Vec<usize> = Orientation::ALL
+            .iter()
+            .filter_map(|&orientation| self.lines.get(&orientation.line_through(point)))
+            .flatten()
+            .map(|&index| self.segments[index])
+            .filter(|segment| segment.contains(point))
+            .map(|segment| segment.steps_to(point))
+            .collect();
+        visits.sort_unstable();
+        visits.dedup();
+        visits
+    }
+
+    pub fn first_visit(&self, point: Point) -> Option<usize> {
+        self.visits(point).first().copied()
+    }
+
+    pub fn last_visit(&self, point: Point) -> Option<usize> {
+        self.visits(point).last().copied()
+    }
+
+    /// The signal delay to `point`, `None` if the wire never gets there.
+    pub fn delay(&self, point: Point, delay: Delay) -> Option<usize> {
+        match delay {
+            Delay::First => self.first_visit(point),
+            Delay::Last => self.last_visit(point),
+            Delay::LoopRemoved => self
+                .visits(point)
+                .into_iter()
+                .map(|steps| self.loop_removed(steps))
+                .min(),
+        }
+    }
+
+    /// The loop-removed delay to wherever the wire is after `steps`.
+    fn loop_removed(&self, steps: usize) -> usize {
+        piece_at(&self.delays, steps).at(steps) as usize
+    }
+
+    /// Where the wire shares cells with itself, the overlaps' `wires` are
+    /// the indices of the two segments.
+    pub fn self_crossings(&self) -> Vec<Overlap> {
+        let pieces: Vec<&[Segment]> = self.segments.chunks(1).collect();
+        find_crossings(&pieces)
+    }
+
+    /// Cells of `segment`, one of the wire's, around which its `delay` may
+    /// stop changing evenly: where other segments of the wire go over it,
+    /// and for loop-removed delays where those change course on the way.
+    /// In between, the delay along the segment rises or falls evenly, or
+    /// rises and then falls.
+    pub(crate) fn delay_breaks(&self, segment: &Segment, delay: Delay) -> Vec<Point> {
+        let index = self
+            .segments
+            .partition_point(|other| other.steps < segment.steps);
+        let mut breaks = Vec::new();
+        let mut visiting = vec![(*segment, segment.steps + 1, segment.steps + segment.len())];
+        for &overlap in &self.touching[index] {
+            let overlap = self.self_overlaps[overlap];
+            breaks.extend([overlap.from, overlap.to]);
+            let other = if overlap.wires.0 == index {
+                overlap.segments.1
+            } else {
+                overlap.segments.0
+            };
+            let (from, to) = (other.steps_to(overlap.from), other.steps_to(overlap.to));
+            visiting.push((other, from.min(to), from.max(to)));
+        }
+        if delay == Delay::LoopRemoved {
+            for (other, from, to) in visiting {
+                let first = self.delays.partition_point(|piece| piece.from < from);
+                let last = self.delays.partition_point(|piece| piece.from <= to);
+                breaks.extend(
+                    self.delays[first..last]
+                        .iter()
+                        .map(|piece| other.at(piece.from)),
+                );
+            }
+        }
+        breaks
+    }
+
+    /// Every time the wire comes back to a cell, in order. There is one for
+    /// every cell the wire shares with itself, they are listed cell by cell.
+    pub fn loops(&self) -> Vec<Loop> {
+        let mut cells: HashSet<Point> = self
+            .self_crossings()
+            .iter()
+            .flat_map(|overlap| overlap.points().collect::<Vec<_>>())
+            .collect();
+        // Coming back to the port closes a loop from where the wire started.
+        let back_at_port = self.first_visit(ORIGIN).is_some();
+        if back_at_port {
+            cells.insert(ORIGIN);
+        }
+        let mut revisits: Vec<(usize, Point)> = cells
+            .into_iter()
+            .flat_map(|point| {
+                let visits = self.visits(point);
+                visits.into_iter().map(move |steps| (steps, point))
+            })
+            .collect();
+        if back_at_port {
+            revisits.push((0, ORIGIN));
+        }
+        revisits.sort_unstable_by_key(|&(steps, point)| (steps, point.x, point.y));
+
+        let mut last: HashMap<Point, usize> = HashMap::new();
+        revisits
+            .into_iter()
+            .filter_map(|(steps, point)| {
+                let from = last.insert(point, steps)?;
+                Some(Loop {
+                    point,
+                    from,
+                    to: steps,
+                })
+            })
+            .collect()
+    }
+
+    /// Where the wire comes back to cells it was on before, stretch by
+    /// stretch: each self-crossing is a stretch of the later segment going
+    /// over the earlier one.
+    fn shortcuts(&self) -> Vec<Shortcut> {
+        let mut shortcuts: Vec<Shortcut> = self
+            .self_overlaps
+            .iter()
+            .map(|overlap| {
+                let (earlier, later) = overlap.segments;
+                let ends = [overlap.from, overlap.to]
+                    .map(|point| (later.steps_to(point), earlier.steps_to(point) as i64));
+                let [(from, back), (to, other)] = if ends[0].0 <= ends[1].0 {
+                    ends
+                } else {
+                    [ends[1], ends[0]]
+                };
+                Shortcut {
+                    from,
+                    to,
+                    back: back as usize,
+                    dir: (other - back).signum(),
+                }
+            })
+            .collect();
+        // Coming back to the port is a shortcut to the start.
+        shortcuts.extend(self.visits(ORIGIN).into_iter().map(|steps| Shortcut {
+            from: steps,
+            to: steps,
+            back: 0,
+            dir: 0,
+        }));
+        shortcuts.sort_unstable_by_key(|shortcut| shortcut.from);
+        shortcuts
+    }
+
+    /// Works out the loop-removed delay after every step. It grows by one
+    /// per step, except where the wire is on a cell it was on before: the
+    /// signal can be as early as it was then. Only the stretches where that
+    /// happens are looked at, never single cells.
+    fn find_delays(&mut self) {
+        let shortcuts = self.shortcuts();
+        let mut delays = vec![Piece {
+            from: 0,
+            delay: 0,
+            slope: 1,
+        }];
+        let mut active: Vec<Shortcut> = Vec::new();
+        let mut next = 0;
+        let mut steps = 1;
+        while steps <= self.len() {
+            active.retain(|shortcut| shortcut.to >= steps);
+            while next < shortcuts.len() && shortcuts[next].from <= steps {
+                active.push(shortcuts[next]);
+                next += 1;
+            }
+            // The same shortcuts apply until one starts or ends.
+            let mut end = active
+                .iter()
+                .map(|shortcut| shortcut.to)
+                .chain(shortcuts.get(next).map(|shortcut| shortcut.from - 1))
+                .fold(self.len(), usize::min);
+
+            // The candidates: one more than the step before, and the
+            // delays the shortcuts lead back to. Each is a straight line
+            // as long as the delays it leads back to are.
+            let before = piece_at(&delays, steps - 1).at(steps - 1);
+            let mut lines = vec![(before + 1, 1)];
+            for shortcut in &active {
+                let back = shortcut.back(steps);
+                let index = delays.partition_point(|piece| piece.from <= back) - 1;
+                let piece = delays[index];
+                let straight = match shortcut.dir {
+                    1 => delays.get(index + 1).map(|next| next.from - back),
+                    -1 => Some(back - piece.from + 1),
+                    _ => None,
+                };
+                if let Some(straight) = straight {
+                    end = end.min(steps + straight - 1);
+                }
+                lines.push((piece.at(back), shortcut.dir * piece.slope));
+            }
+            push_lowest(&mut delays, steps, end, &lines);
+            steps = end + 1;
+        }
+        self.delays = delays;
+    }
+}
+
+/// The piece of `pieces` that covers `steps`.
+fn piece_at(pieces: &[Piece], steps: usize) -> Piece {
+    pieces[pieces.partition_point(|piece| piece.from <= steps) - 1]
+}
+
+/// Adds the lowest of `lines` (delay at `from`, slope) from `from` to `to`
+/// to `pieces`. Each line can only be overtaken by one falling faster.
+fn push_lowest(pieces: &mut Vec<Piece>, from: usize, to: usize, lines: &[(i64, i64)]) {
+    let mut steps = from;
+    while steps <= to {
+        let offset = (steps - from) as i64;
+        let at = |&(delay, slope): &(i64, i64)| (delay + slope * offset, slope);
+        let (delay, slope) = lines.iter().map(at).min().expect("there is a line");
+        // The first step a line with a lower slope is below this one.
+        let overtaken = lines
+            .iter()
+            .map(at)
+            .filter(|&(_, other)| other < slope)
+            .map(|(other_delay, other)| {
+                steps + ((other_delay - delay) / (slope - other)) as usize + 1
+            })
+            .min()
+            .unwrap_or(usize::MAX);
+        let last = pieces.last().expect("there is a first piece");
+        if last.slope != slope || last.at(steps) != delay {
+            pieces.push(Piece {
+                from: steps,
+                delay,
+                slope,
+            });
+        }
+        steps = overtaken.min(to + 1);
+    }
+}
@@ -0,0 +1,567 @@
+use crate::metric::{Metric, Score};
+use crate::segment::{distance, find_crossings, towards, Overlap, Segment};
+use crate::wire::{calculate_manhattan_distance, Delay, Instruction, Wire};
+use crate::{Crossing, Point, ORIGIN};
+use anyhow::{bail, Result};
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet};
+
+/*
+Any number of wires from the same central port. The puzzle asks about the
+first two, further wires can be compared with them or each other: where two
+of them cross, where at least `k` of them meet, and the lowest combined
+delay (steps to a cell on the way there, summed over the wires, see `Delay`
+for which visit counts) at which a chosen group of wires all meets.
+
+Crossings of two wires stay segments (see `segment.rs`), so do the cells a
+whole group of wires shares. Looking for the best of those cells only looks
+at the few of every stretch where its distance or delay can turn, so it
+takes time in proportion to the crossings, not to how much wire is shared.
+*/
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WireSet {
+    wires: Vec<Wire>,
+}
+
+/// Cells exactly `wires` pass through, in a straight line from `from` to
+/// `to` like an `Overlap`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Meeting {
+    /// Lowest first.
+    pub wires: Vec<usize>,
+    pub from: Point,
+    pub to: Point,
+}
+
+impl Meeting {
+    pub fn len(&self) -> usize {
+        distance(self.from, self.to) + 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Every cell, from `from` to `to`.
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.len() as i64).map(move |i| towards(self.from, self.to, i))
+    }
+}
+
+/// The overlaps of a group's first wire with another wire, by the segment
+/// of the first wire they're on, with the other wire's segment.
+type Shared = HashMap<usize, Vec<(Overlap, Segment)>>;
+
+/// Cells all wires of a group pass through, in a straight line from `from`
+/// to `to`, and the segment of each wire of the group that covers them.
+#[derive(Debug, Clone)]
+struct Stretch {
+    from: Point,
+    to: Point,
+    segments: Vec<Segment>,
+}
+
+impl Stretch {
+    fn len(&self) -> i64 {
+        distance(self.from, self.to) as i64 + 1
+    }
+
+    fn cell(&self, i: i64) -> Point {
+        towards(self.from, self.to, i)
+    }
+
+    /// One step along the stretch, `(0, 0)` for a single cell.
+    fn direction(&self) -> (i64, i64) {
+        (
+            self.to.x.cmp(&self.from.x) as i64,
+            self.to.y.cmp(&self.from.y) as i64,
+        )
+    }
+
+    /// Where `point` is along the stretch, if it's one of its cells.
+    fn find(&self, point: Point) -> Option<i64> {
+        let (dx, dy) = self.direction();
+        let (px, py) = (
+            i64::from(point.x) - i64::from(self.from.x),
+            i64::from(point.y) - i64::from(self.from.y),
+        );
+        let i = if dx != 0 { px * dx } else { py * dy };
+        (i * dx == px && i * dy == py && (0..self.len()).contains(&i)).then_some(i)
+    }
+
+    /// The cells of the stretch that `other` has as well, as a range of
+    /// indices. Straight lines share a range if they're the same line,
+    /// otherwise at most a cell.
+    fn shared_with(&self, other: &Stretch) -> Option<(i64, i64)> {
+        let ((dx, dy), (ex, ey)) = (self.direction(), other.direction());
+        let (px, py) = (
+            i64::from(other.from.x) - i64::from(self.from.x),
+            i64::from(other.from.y) - i64::from(self.from.y),
+        );
+        // `from + i * d = other.from + j * e`, solved for `i`.
+        let det = ey * dx - ex * dy;
+        if det == 0 {
+            let ends = (self.find(other.from), self.find(other.to));
+            if let (Some(a), Some(b)) = ends {
+                return Some((a.min(b), a.max(b)));
+            }
+            let (low, high) = (self.index(other.from), self.index(other.to));
+            let (low, high) = (low.min(high).max(0), low.max(high).min(self.len() - 1));
+            let collinear = (low..=high)
+                .next()
+                .is_some_and(|i| other.find(self.cell(i)).is_some());
+            return collinear.then_some((low, high));
+        }
+        let i = ey * px - ex * py;
+        if i % det != 0 {
+            return None;
+        }
+        let i = i / det;
+        ((0..self.len()).contains(&i) && other.find(self.cell(i)).is_some()).then_some((i, i))
+    }
+
+    /// How far along the stretch `point`, which is on its line, is.
+    fn index(&self, point: Point) -> i64 {
+        let along =
+            |from: i32, to: i32, at: i32| (i64::from(at) - i64::from(from)) * to.cmp(&from) as i64;
+        if self.from.x != self.to.x {
+            along(self.from.x, self.to.x, point.x)
+        } else {
+            along(self.from.y, self.to.y, point.y)
+        }
+    }
+
+    /// The part of the stretch `overlap` covers as well. `overlap` is on
+    /// the same segment of the group's first wire, `other` is the segment
+    /// of the wire that joins the group.
+    fn narrow(&self, overlap: &Overlap, other: Segment) -> Option<Stretch> {
+        let first = self.segments[0];
+        let range = |a: Point, b: Point| {
+            let (a, b) = (first.steps_to(a), first.steps_to(b));
+            (a.min(b), a.max(b))
+        };
+        let (low, high) = range(self.from, self.to);
+        let (other_low, other_high) = range(overlap.from, overlap.to);
+        let (low, high) = (low.max(other_low), high.min(other_high));
+        (low <= high).then(|| {
+            let mut segments = self.segments.clone();
+            segments.push(other);
+            Stretch {
+                from: first.at(low),
+                to: first.at(high),
+                segments,
+            }
+        })
+    }
+
+    /// The cells to score for the `k` best of the stretch. Between its
+    /// ends and `breaks` scores only rise, fall, or rise and then fall, so
+    /// the best are no further than `k` from one of them.
+    fn candidates(&self, breaks: Vec<i64>, k: usize) -> Vec<Point> {
+        let last = self.len() - 1;
+        let k = k as i64;
+        let mut windows: Vec<(i64, i64)> = breaks
+            .into_iter()
+            .chain([0, last])
+            .map(|at| {
+                let at = at.clamp(0, last);
+                ((at - k).max(0), (at + k).min(last))
+            })
+            .collect();
+        windows.sort_unstable();
+        let mut cells = Vec::new();
+        let mut next = 0;
+        for (low, high) in windows {
+            cells.extend((low.max(next)..=high).map(|i| self.cell(i)));
+            next = next.max(high + 1);
+        }
+        cells
+    }
+}
+
+/// The parts of `stretches` that the overlaps in `shared` cover as well.
+fn narrow_all(stretches: &[Stretch], shared: &Shared) -> Vec<Stretch> {
+    stretches
+        .iter()
+        .flat_map(|stretch| {
+            shared
+                .get(&stretch.segments[0].steps)
+                .into_iter()
+                .flatten()
+                .filter_map(|(overlap, other)| stretch.narrow(overlap, *other))
+        })
+        .collect()
+}
+
+/// Where along `stretch` the distance to `reference` can turn: where it
+/// crosses the row, column or diagonals of `reference`, and on either side
+/// of the cell closest as the crow flies.
+fn distance_breaks(stretch: &Stretch, reference: Point) -> Vec<i64> {
+    let (from, to) = (stretch.from, stretch.to);
+    let (dx, dy) = (to.x.cmp(&from.x) as i64, to.y.cmp(&from.y) as i64);
+    let ax = i64::from(from.x) - i64::from(reference.x);
+    let ay = i64::from(from.y) - i64::from(reference.y);
+    // The `i`th cell is on each of them where `a + i * b = 0`.
+    [
+        (ax, dx),
+        (ay, dy),
+        (ax - ay, dx - dy),
+        (ax + ay, dx + dy),
+        (ax * dx + ay * dy, dx * dx + dy * dy),
+    ]
+    .into_iter()
+    .filter(|&(_, b)| b != 0)
+    .flat_map(|(a, b)| {
+        let (a, b) = if b < 0 { (-a, -b) } else { (a, b) };
+        let below = (-a).div_euclid(b);
+        [below, below + 1]
+    })
+    .collect()
+}
+
+impl WireSet {
+    pub fn trace(wires: &[Vec<Instruction>]) -> Result<Self> {
+        let wires = wires
+            .iter()
+            .map(|wire| Wire::trace(wire))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(WireSet { wires })
+    }
+
+    pub fn len(&self) -> usize {
+        self.wires.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.wires.is_empty()
+    }
+
+    pub fn wire(&self, wire: usize) -> &Wire {
+        &self.wires[wire]
+    }
+
+    fn segments(&self) -> Vec<&[Segment]> {
+        self.wires.iter().map(Wire::segments).collect()
+    }
+
+    /// Where any two different wires share cells.
+    pub fn pairwise_crossings(&self) -> Vec<Overlap> {
+        find_crossings(&self.segments())
+    }
+
+    /// Where wires `a` and `b` share cells.
+    pub fn crossings(&self, a: usize, b: usize) -> Vec<Overlap> {
+        let (a, b) = (a.min(b), a.max(b));
+        let mut overlaps = find_crossings(&[self.wires[a].segments(), self.wires[b].segments()]);
+        for overlap in &mut overlaps {
+            overlap.wires = (a, b);
+        }
+        overlaps
+    }
+
+    /// Every stretch of cells all of `wires` pass through.
+    fn stretches(&self, wires: &[usize]) -> Vec<Stretch> {
+        let (first, second) = (wires[0], wires[1]);
+        let mut stretches: Vec<Stretch> = self
+            .crossings(first, second)
+            .iter()
+            .map(|overlap| {
+                let (a, b) = overlap.segments;
+                Stretch {
+                    from: overlap.from,
+                    to: overlap.to,
+                    segments: if first < second {
+                        vec![a, b]
+                    } else {
+                        vec![b, a]
+                    },
+                }
+            })
+            .collect();
+        for &wire in &wires[2..] {
+            stretches = self.join(&stretches, first, wire);
+        }
+        stretches
+    }
+
+    /// The parts of `stretches`, of a group starting with wire `first`,
+    /// that `wire` passes through as well.
+    fn join(&self, stretches: &[Stretch], first: usize, wire: usize) -> Vec<Stretch> {
+        narrow_all(stretches, &self.shared(first, wire))
+    }
+
+    fn shared(&self, first: usize, wire: usize) -> Shared {
+        // Both are on segments of the first wire, only the same ones can
+        // share cells.
+        let mut by_segment = Shared::new();
+        for overlap in self.crossings(first, wire) {
+            let (a, b) = overlap.segments;
+            let (own, other) = if first < wire { (a, b) } else { (b, a) };
+            by_segment
+                .entry(own.steps)
+                .or_default()
+                .push((overlap, other));
+        }
+        by_segment
+    }
+
+    /// Where at least `k` different wires meet, closest to the central
+    /// port first. Every cell is in one meeting, with all wires that pass
+    /// through it.
+    pub fn meetings(&self, k: usize) -> Result<Vec<Meeting>> {
+        if k < 2 {
+            bail!("Wires meet where at least two of them do, not {k}");
+        }
+        let mut shared: HashMap<(usize, usize), Shared> = HashMap::new();
+        for a in 0..self.len() {
+            for b in (0..self.len()).filter(|&b| b != a) {
+                shared.insert((a, b), self.shared(a, b));
+            }
+        }
+        let mut groups: Vec<(Vec<usize>, Vec<Stretch>)> = Vec::new();
+        for a in 0..self.len() {
+            for b in a + 1..self.len() {
+                groups.push((vec![a, b], self.stretches(&[a, b])));
+            }
+        }
+        let mut meetings: Vec<((u64, i32, i32), Meeting)> = Vec::new();
+        while let Some((group, stretches)) = groups.pop() {
+            if stretches.is_empty() {
+                continue;
+            }
+            if group.len() >= k {
+                for part in self.only_group(&group, &stretches, &shared) {
+                    let closest = part
+                        .candidates(distance_breaks(&part, ORIGIN), 0)
+                        .into_iter()
+                        .map(|point| (calculate_manhattan_distance(&point), point.x, point.y))
+                        .min()
+                        .expect("stretches have cells");
+                    let meeting = Meeting {
+                        wires: group.clone(),
+                        from: part.from,
+                        to: part.to,
+                    };
+                    meetings.push((closest, meeting));
+                }
+            }
+            for wire in group[group.len() - 1] + 1..self.len() {
+                let joined = narrow_all(&stretches, &shared[&(group[0], wire)]);
+                let mut larger = group.clone();
+                larger.push(wire);
+                groups.push((larger, joined));
+            }
+        }
+        meetings.sort_unstable_by_key(|&(closest, _)| closest);
+        Ok(meetings.into_iter().map(|(_, meeting)| meeting).collect())
+    }
+
+    /// The parts of `stretches` only `group` passes through, each cell
+    /// once. Cells more wires pass through belong to a larger group, ones
+    /// the group passes twice to the earlier stretch.
+    fn only_group(
+        &self,
+        group: &[usize],
+        stretches: &[Stretch],
+        shared: &HashMap<(usize, usize), Shared>,
+    ) -> Vec<Stretch> {
+        let mut parts = Vec::new();
+        for (index, stretch) in stretches.iter().enumerate() {
+            let others = (0..self.len()).filter(|wire| !group.contains(wire));
+            let mut covered: Vec<(i64, i64)> = others
+                .flat_map(|wire| {
+                    narrow_all(std::slice::from_ref(stretch), &shared[&(group[0], wire)])
+                })
+                .filter_map(|part| stretch.shared_with(&part))
+                .chain(
+                    stretches[..index]
+                        .iter()
+                        .filter_map(|earlier| stretch.shared_with(earlier)),
+                )
+                .collect();
+            covered.sort_unstable();
+            let mut next = 0;
+            for (low, high) in covered.into_iter().chain([(stretch.len(), 0)]) {
+                if low > next {
+                    parts.push(Stretch {
+                        from: stretch.cell(next),
+                        to: stretch.cell(low - 1),
+                        segments: Vec::new(),
+                    });
+                }
+                next = next.max(high + 1);
+            }
+        }
+        parts
+    }
+
+    /// Steps each wire takes to first get to `point`, by wire index, for
+    /// the wires that do.
+    pub fn steps_to(&self, point: Point) -> BTreeMap<usize, usize> {
+        (0..self.len())
+            .filter_map(|wire| Some((wire, self.wires[wire].first_visit(point)?)))
+            .collect()
+    }
+
+    fn check_group(&self, wires: &[usize]) -> Result<()> {
+        if wires.len() < 2 {
+            bail!("Pick at least two wires");
+        }
+        for (index, &wire) in wires.iter().enumerate() {
+            if wire >= self.len() {
+                bail!("There is no wire {wire}, only {}", self.len());
+            }
+            if wires[..index].contains(&wire) {
+                bail!("Wire {wire} is picked twice");
+            }
+        }
+        Ok(())
+    }
+
+    /// The cell closest to the central port that all of `wires` pass
+    /// through.
+    pub fn closest_crossing(&self, wires: &[usize]) -> Result<Option<Crossing<u64>>> {
+        self.check_group(wires)?;
+        let best = if let [a, b] = *wires {
+            self.crossings(a, b)
+                .iter()
+                .map(|overlap| {
+                    let (value, point) = overlap.closest();
+                    Crossing { value, point }
+                })
+                .min_by_key(|crossing| (crossing.value, crossing.point.x, crossing.point.y))
+        } else {
+            self.best_cells(
+                wires,
+                1,
+                |stretch| distance_breaks(stretch, ORIGIN),
+                |point| Some(calculate_manhattan_distance(&point)),
+            )
+            .into_iter()
+            .next()
+        };
+        Ok(best)
+    }
+
+    /// The cell all of `wires` reach with the lowest combined `delay`.
+    pub fn fewest_combined_steps(
+        &self,
+        wires: &[usize],
+        delay: Delay,
+    ) -> Result<Option<Crossing<usize>>> {
+        self.check_group(wires)?;
+        let best = match (delay, wires) {
+            // Overlaps know the first visits of their two wires.
+            (Delay::First, &[a, b]) => self
+                .crossings(a, b)
+                .iter()
+                .map(|overlap| {
+                    let (value, point) = overlap.fewest_steps();
+                    Crossing { value, point }
+                })
+                .min_by_key(|crossing| (crossing.value, crossing.point.x, crossing.point.y)),
+            _ => self
+                .best_cells(
+                    wires,
+                    1,
+                    |stretch| self.delay_breaks(stretch, wires, delay),
+                    |point| self.combined_delay(point, wires, delay),
+                )
+                .into_iter()
+                .next(),
+        };
+        Ok(best)
+    }
+
+    fn combined_delay(&self, point: Point, wires: &[usize], delay: Delay) -> Option<usize> {
+        wires
+            .iter()
+            .map(|&wire| self.wires[wire].delay(point, delay))
+            .sum()
+    }
+
+    /// Where along `stretch` the combined delay of `wires` can turn.
+    fn delay_breaks(&self, stretch: &Stretch, wires: &[usize], delay: Delay) -> Vec<i64> {
+        stretch
+            .segments
+            .iter()
+            .zip(wires)
+            .flat_map(|(segment, &wire)| self.wires[wire].delay_breaks(segment, delay))
+            .map(|point| stretch.index(point))
+            .collect()
+    }
+
+    /// The `k` best cells all of `wires` pass through by `metric`, best
+    /// first. Distances are measured from `reference`, delays always from
+    /// the central port. Every shared cell is looked at, but only `k` are
+    /// kept.
+    pub fn best_crossings(
+        &self,
+        wires: &[usize],
+        metric: Metric,
+        reference: Point,
+        k: usize,
+    ) -> Result<Vec<Crossing<Score>>> {
+        self.check_group(wires)?;
+        Ok(match metric {
+            Metric::Delay(delay) => self.best_cells(
+                wires,
+                k,
+                |stretch| self.delay_breaks(stretch, wires, delay),
+                |point| self.combined_delay(point, wires, delay).map(Score::Delay),
+            ),
+            _ => self.best_cells(
+                wires,
+                k,
+                |stretch| distance_breaks(stretch, reference),
+                |point| metric.distance(point, reference),
+            ),
+        })
+    }
+
+    /// The `k` best cells all of `wires` pass through by `score`, best
+    /// first. Only the candidates of every stretch are scored, `breaks`
+    /// says where along a stretch its scores can turn.
+    fn best_cells<S: Ord>(
+        &self,
+        wires: &[usize],
+        k: usize,
+        breaks: impl Fn(&Stretch) -> Vec<i64>,
+        score: impl Fn(Point) -> Option<S>,
+    ) -> Vec<Crossing<S>> {
+        if k == 0 {
+            return Vec::new();
+        }
+        // The worst of the best so far on top.
+        let mut best: BinaryHeap<(S, i32, i32)> = BinaryHeap::new();
+        let mut kept: HashSet<Point> = HashSet::new();
+        for stretch in self.stretches(wires) {
+            for point in stretch.candidates(breaks(&stretch), k) {
+                if kept.contains(&point) {
+                    continue;
+                }
+                let Some(score) = score(point) else {
+                    continue;
+                };
+                let entry = (score, point.x, point.y);
+                if best.len() == k {
+                    if best.peek().is_some_and(|worst| entry >= *worst) {
+                        continue;
+                    }
+                    if let Some((_, x, y)) = best.pop() {
+                        kept.remove(&Point::new(x, y));
+                    }
+                }
+                kept.insert(point);
+                best.push(entry);
+            }
+        }
+        best.into_sorted_vec()
+            .into_iter()
+            .map(|(value, x, y)| Crossing {
+                value,
+                point: Point::new(x, y),
+            })
+            .collect()
+    }
+}
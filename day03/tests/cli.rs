@@ -0,0 +1,22 @@
+use day03::cli::{parse_args, rank};
+use day03::metric::Metric;
+use day03::wire::parse_wires;
+use day03::wire_set::WireSet;
+use day03::ORIGIN;
+
+fn args<'a>(args: &'a [&str]) -> impl Iterator<Item = String> + 'a {
+    args.iter().map(|arg| arg.to_string())
+}
+
+#[test]
+fn an_explicit_from_at_the_port_still_ranks() {
+    let set = WireSet::trace(&parse_wires("R8,U5,L5,D3\nU7,R6,D4,L4\n").unwrap()).unwrap();
+
+    let plain = parse_args(args(&["input.txt"]), "usage").unwrap();
+    assert_eq!(plain.from, None);
+    assert!(!rank(&set, &plain, Metric::Manhattan).unwrap());
+
+    let from_port = parse_args(args(&["--from", "0,0"]), "usage").unwrap();
+    assert_eq!(from_port.from, Some(ORIGIN));
+    assert!(rank(&set, &from_port, Metric::Manhattan).unwrap());
+}
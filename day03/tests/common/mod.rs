@@ -0,0 +1,54 @@
+use day03::direction::Direction::{self, *};
+use day03::wire::Instruction;
+use day03::{Point, ORIGIN};
+
+/// Short random wires that often run along each other and come back to
+/// the port, from a fixed seed.
+pub fn random_wires(seed: u64, count: usize) -> Vec<Vec<Instruction>> {
+    random_wires_going(&[Up, Down, Left, Right], seed, count)
+}
+
+/// Like `random_wires`, but also going diagonally.
+#[allow(dead_code)]
+pub fn random_diagonal_wires(seed: u64, count: usize) -> Vec<Vec<Instruction>> {
+    random_wires_going(
+        &[Up, UpRight, Right, DownRight, Down, DownLeft, Left, UpLeft],
+        seed,
+        count,
+    )
+}
+
+fn random_wires_going(directions: &[Direction], seed: u64, count: usize) -> Vec<Vec<Instruction>> {
+    let mut state = seed;
+    let mut next = |bound: u64| {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 33) % bound
+    };
+    (0..count)
+        .map(|_| {
+            (0..12)
+                .map(|_| Instruction {
+                    direction: directions[next(directions.len() as u64) as usize],
+                    steps: next(6) as i32,
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// The cell a wire is on after every step, `path[i]` after `i + 1` steps.
+/// The reference the segment model is checked against.
+pub fn step_path(wire: &[Instruction]) -> Vec<Point> {
+    let mut position = ORIGIN;
+    let mut path = Vec::new();
+    for instruction in wire {
+        let (dx, dy) = instruction.direction.delta();
+        for _ in 0..instruction.steps {
+            position = Point::new(position.x + dx, position.y + dy);
+            path.push(position);
+        }
+    }
+    path
+}
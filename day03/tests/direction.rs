@@ -0,0 +1,57 @@
+use day03::direction::Direction::{self, *};
+use day03::wire::{parse_wires, Instruction};
+
+fn moves(input: &str) -> Vec<(Direction, i32)> {
+    parse_wires(input).unwrap()[0]
+        .iter()
+        .map(|&Instruction { direction, steps }| (direction, steps))
+        .collect()
+}
+
+#[test]
+fn compass_points_and_diagonals_are_directions() {
+    assert_eq!(
+        moves("N3,E2,S1,W4,U1"),
+        [(Up, 3), (Right, 2), (Down, 1), (Left, 4), (Up, 1)]
+    );
+    assert_eq!(
+        moves("NE3,SW2,NW10,SE0"),
+        [(UpRight, 3), (DownLeft, 2), (UpLeft, 10), (DownRight, 0)]
+    );
+    assert_eq!(UpRight.delta(), (1, 1));
+    assert_eq!(Up.turned(-2), Left);
+    assert_eq!(UpLeft.turned(3), Right);
+}
+
+#[test]
+fn turtle_wires_turn_by_degrees_starting_upwards() {
+    assert_eq!(
+        moves("F10,R90,F2,L45,F3,L360,R180,F1"),
+        [(Up, 10), (Right, 2), (UpRight, 3), (DownLeft, 1)]
+    );
+    // The same crossings as the puzzle's first example.
+    let turtle = parse_wires("R90,F8,L90,F5,L90,F5,L90,F3\nF7,R90,F6,R90,F4,R90,F4").unwrap();
+    assert_eq!(turtle, parse_wires("R8,U5,L5,D3\nU7,R6,D4,L4").unwrap());
+}
+
+#[test]
+fn bad_tokens_are_errors_at_their_position() {
+    let error = |input: &str| parse_wires(input).unwrap_err().to_string();
+    assert_eq!(
+        error("R8,U5\nR8,X12"),
+        "2:4: invalid token \"X12\": expected one of U, D, L, R, N, S, W, E, NE, NW, SE, SW"
+    );
+    assert_eq!(
+        error("F10,L30"),
+        "1:5: invalid token \"L30\": turns must be multiples of 45 degrees"
+    );
+    assert_eq!(
+        error("R-3"),
+        "1:2: invalid token \"-3\": step count must not be negative"
+    );
+    // Turtle wires only turn and move forward.
+    assert_eq!(
+        error("N3,F2"),
+        "1:1: invalid token \"N3\": expected one of L, R, F"
+    );
+}
@@ -0,0 +1,172 @@
+mod common;
+
+use common::{random_diagonal_wires, random_wires, step_path};
+use day03::metric::{Metric, Score};
+use day03::wire::{parse_wires, Delay};
+use day03::wire_set::WireSet;
+use day03::{Point, ORIGIN};
+use std::collections::HashSet;
+
+const SECOND_EXAMPLE: &str = "R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83";
+
+fn wire_set(input: &str) -> WireSet {
+    WireSet::trace(&parse_wires(input).unwrap()).unwrap()
+}
+
+fn ranking(set: &WireSet, metric: Metric, reference: Point, k: usize) -> Vec<(Point, String)> {
+    set.best_crossings(&[0, 1], metric, reference, k)
+        .unwrap()
+        .iter()
+        .map(|crossing| (crossing.point, crossing.value.to_string()))
+        .collect()
+}
+
+#[test]
+fn distances_follow_their_metric() {
+    let (a, b) = (Point::new(3, -4), Point::new(0, 0));
+    assert_eq!(Metric::Manhattan.distance(a, b), Some(Score::Distance(7)));
+    assert_eq!(Metric::Chebyshev.distance(a, b), Some(Score::Distance(4)));
+    assert_eq!(
+        Metric::Euclidean.distance(a, b),
+        Some(Score::EuclideanSquared(25))
+    );
+    assert_eq!(Score::EuclideanSquared(25).to_string(), "5");
+    assert_eq!(Score::EuclideanSquared(2).to_string(), "1.414");
+    assert_eq!(Metric::Delay(Delay::First).distance(a, b), None);
+
+    // No overflow across the whole grid.
+    let far = Metric::Euclidean.distance(
+        Point::new(i32::MIN, i32::MIN),
+        Point::new(i32::MAX, i32::MAX),
+    );
+    assert_eq!(
+        far,
+        Some(Score::EuclideanSquared(2 * (u128::from(u32::MAX)).pow(2)))
+    );
+}
+
+#[test]
+fn the_k_best_crossings_are_ranked() {
+    let set = wire_set(SECOND_EXAMPLE);
+    assert_eq!(
+        ranking(&set, Metric::Manhattan, ORIGIN, 2),
+        [
+            (Point::new(155, 4), "159".to_string()),
+            (Point::new(155, 11), "166".to_string())
+        ]
+    );
+    assert_eq!(
+        ranking(&set, Metric::Euclidean, ORIGIN, 1),
+        [(Point::new(146, 46), "153.075".to_string())]
+    );
+    assert_eq!(
+        ranking(&set, Metric::Delay(Delay::First), ORIGIN, 1),
+        [(Point::new(158, -12), "610".to_string())]
+    );
+    // Asking for more than there are lists them all, and none twice.
+    assert_eq!(ranking(&set, Metric::Chebyshev, ORIGIN, 10).len(), 4);
+    assert_eq!(ranking(&set, Metric::Manhattan, ORIGIN, 0), []);
+}
+
+#[test]
+fn distances_are_measured_from_the_reference() {
+    let set = wire_set(SECOND_EXAMPLE);
+    let from = Point::new(158, -12);
+    assert_eq!(
+        ranking(&set, Metric::Manhattan, from, 1),
+        [(from, "0".to_string())]
+    );
+    // Delays start at the port wherever the reference is.
+    assert_eq!(
+        ranking(&set, Metric::Delay(Delay::First), Point::new(155, 4), 1),
+        ranking(&set, Metric::Delay(Delay::First), ORIGIN, 1)
+    );
+}
+
+#[test]
+fn rankings_match_scoring_every_shared_cell() {
+    let metrics = [
+        Metric::Manhattan,
+        Metric::Chebyshev,
+        Metric::Euclidean,
+        Metric::Delay(Delay::First),
+        Metric::Delay(Delay::Last),
+        Metric::Delay(Delay::LoopRemoved),
+    ];
+    for seed in 0..300 {
+        let wires = if seed % 2 == 0 {
+            random_wires(seed, 3)
+        } else {
+            random_diagonal_wires(seed, 3)
+        };
+        let set = WireSet::trace(&wires).unwrap();
+        let paths: Vec<HashSet<Point>> = wires
+            .iter()
+            .map(|wire| step_path(wire).into_iter().collect())
+            .collect();
+        let reference = Point::new(seed as i32 % 7 - 3, seed as i32 % 5 - 2);
+        for group in [&[0, 1][..], &[2, 0], &[0, 1, 2]] {
+            let shared: Vec<Point> = paths[group[0]]
+                .iter()
+                .filter(|point| group.iter().all(|&wire| paths[wire].contains(point)))
+                .copied()
+                .collect();
+            for metric in metrics {
+                let mut expected: Vec<(Score, i32, i32)> = shared
+                    .iter()
+                    .filter_map(|&point| {
+                        let score = match metric {
+                            Metric::Delay(delay) => Score::Delay(
+                                group
+                                    .iter()
+                                    .map(|&wire| set.wire(wire).delay(point, delay))
+                                    .sum::<Option<usize>>()?,
+                            ),
+                            _ => metric.distance(point, reference)?,
+                        };
+                        Some((score, point.x, point.y))
+                    })
+                    .collect();
+                expected.sort_unstable();
+                for k in 1..=4 {
+                    let found: Vec<(Score, i32, i32)> = set
+                        .best_crossings(group, metric, reference, k)
+                        .unwrap()
+                        .iter()
+                        .map(|crossing| (crossing.value, crossing.point.x, crossing.point.y))
+                        .collect();
+                    let best = &expected[..k.min(expected.len())];
+                    assert_eq!(found, best, "seed {seed} {group:?} {metric} k {k}");
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn long_stretches_are_not_walked_cell_by_cell() {
+    // Three wires sharing a billion cells along the `x` axis.
+    let set = wire_set("R1000000000\nR1000000000,L5\nU1,R7,D1,R1000000000");
+    let ranking: Vec<(Point, String)> = set
+        .best_crossings(&[0, 1, 2], Metric::Manhattan, Point::new(500, 3), 3)
+        .unwrap()
+        .iter()
+        .map(|crossing| (crossing.point, crossing.value.to_string()))
+        .collect();
+    assert_eq!(
+        ranking,
+        [
+            (Point::new(500, 0), "3".to_string()),
+            (Point::new(499, 0), "4".to_string()),
+            (Point::new(501, 0), "4".to_string())
+        ]
+    );
+    // Only the last few cells are on the second wire more than once.
+    let last = set
+        .best_crossings(&[0, 1], Metric::Delay(Delay::Last), ORIGIN, 1000)
+        .unwrap();
+    assert_eq!(last.len(), 1000);
+    assert_eq!(last[999].value, Score::Delay(2000));
+    let closest = set.closest_crossing(&[0, 1, 2]).unwrap().unwrap();
+    assert_eq!((closest.value, closest.point), (7, Point::new(7, 0)));
+}
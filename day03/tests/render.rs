@@ -0,0 +1,103 @@
+use day03::render::{Picture, SVG_SIZE};
+use day03::wire::{parse_wires, Delay};
+use day03::wire_set::WireSet;
+
+fn wire_set(input: &str) -> WireSet {
+    WireSet::trace(&parse_wires(input).unwrap()).unwrap()
+}
+
+#[test]
+fn small_wires_are_drawn_cell_by_cell() {
+    let set = wire_set("R8,U5,L5,D3\nU7,R6,D4,L4");
+    let picture = Picture::new(&set).highlight(&[0, 1], Delay::First).unwrap();
+    assert_eq!(
+        picture.to_terminal(120, 60, false),
+        "\
++-----+
+|     |
+|  +--@-+
+|  |  | |
+| -*--+ |
+|  |    |
+|       |
+o-------+
+"
+    );
+    let colored = picture.to_terminal(120, 60, true);
+    assert!(colored.contains("\x1b[31m") && colored.contains("\x1b[32m"));
+}
+
+#[test]
+fn huge_wires_are_scaled_to_fit() {
+    let set = wire_set("R1000000000,U5,L999999990\nU2,R2000000000\nD7");
+    let picture = Picture::new(&set).highlight(&[0, 1], Delay::First).unwrap();
+
+    let preview = picture.to_terminal(80, 20, false);
+    let lines: Vec<&str> = preview.lines().collect();
+    assert!(lines.len() <= 21, "{preview}");
+    assert!(
+        lines.iter().all(|line| line.chars().count() <= 80),
+        "{preview}"
+    );
+    assert!(lines.last().unwrap().starts_with("(1 character = "));
+    assert!(preview.contains('o') && preview.contains('*'), "{preview}");
+
+    let svg = picture.to_svg();
+    assert_eq!(svg.matches("<polyline").count(), 3);
+    assert!(svg.contains("closest: 1000000002 at 1000000000,2"));
+    assert!(svg.contains("lowest delay: 2000000004 at 1000000000,2"));
+    let width: f64 = svg
+        .split("width=\"")
+        .nth(1)
+        .and_then(|rest| rest.split('"').next())
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(width <= SVG_SIZE + 100.0, "{width}");
+}
+
+#[test]
+fn wires_that_never_cross_are_drawn_without_highlights() {
+    let set = wire_set("R2\nL2");
+    let picture = Picture::new(&set).highlight(&[0, 1], Delay::First).unwrap();
+    assert_eq!(picture.to_terminal(10, 10, false), "--o--\n");
+    assert!(!picture.to_svg().contains("closest"));
+}
+
+#[test]
+fn a_single_wire_is_drawn_without_highlights() {
+    let set = wire_set("R2,U1");
+    let picture = Picture::new(&set);
+    assert_eq!(picture.to_terminal(10, 10, false), "  |\no-+\n");
+    assert!(picture.highlight(&[0, 1], Delay::First).is_err());
+}
+
+#[test]
+fn png_pictures_are_laid_out_like_svg_ones() {
+    let set = wire_set("R8,U5,L5,D3\nU7,R6,D4,L4");
+    let picture = Picture::new(&set).highlight(&[0, 1], Delay::First).unwrap();
+    let png = picture.to_png().unwrap();
+    let mut reader = png::Decoder::new(png.as_slice()).read_info().unwrap();
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut pixels).unwrap();
+
+    let svg = picture.to_svg();
+    let size = |attribute: &str| -> u32 {
+        svg.split(&format!("{attribute}=\""))
+            .nth(1)
+            .and_then(|rest| rest.split('"').next())
+            .unwrap()
+            .parse()
+            .unwrap()
+    };
+    assert_eq!((info.width, info.height), (size("width"), size("height")));
+    let pixel = |x: u32, y: u32| {
+        let start = ((y * info.width + x) * 3) as usize;
+        [pixels[start], pixels[start + 1], pixels[start + 2]]
+    };
+    // The wires go right from the port, the corners of the picture are blank.
+    assert_eq!(pixel(0, 0), [255, 255, 255]);
+    let wire = pixel(200, info.height - 40);
+    assert_ne!(wire, [255, 255, 255]);
+    assert_ne!(wire, [0, 0, 0]);
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use aoc_common::Solution;
+use common::{random_diagonal_wires, random_wires, step_path};
+use day03::segment::{find_crossings, trace_segments};
+use day03::wire::{calculate_manhattan_distance, parse_wires};
+use day03::{Day03, Point};
+use std::collections::HashSet;
+
+/// Steps to the first time `path` is on `point`.
+fn first_steps(path: &[Point], point: &Point) -> Option<usize> {
+    path.iter().position(|p| p == point).map(|index| index + 1)
+}
+
+#[test]
+fn crossings_match_the_unit_step_paths() {
+    let seeds = 0..500;
+    for (seed, wires) in seeds
+        .clone()
+        .map(|seed| (seed, random_wires(seed, 2)))
+        .chain(seeds.map(|seed| (seed, random_diagonal_wires(seed, 2))))
+    {
+        let paths: Vec<Vec<Point>> = wires.iter().map(|wire| step_path(wire)).collect();
+        let first: HashSet<Point> = paths[0].iter().copied().collect();
+        let expected: HashSet<Point> = paths[1]
+            .iter()
+            .filter(|point| first.contains(point))
+            .copied()
+            .collect();
+
+        let segments: Vec<_> = wires
+            .iter()
+            .map(|wire| trace_segments(wire).unwrap())
+            .collect();
+        let crossings = find_crossings(&segments);
+        let found: HashSet<Point> = crossings
+            .iter()
+            .flat_map(|overlap| overlap.points())
+            .collect();
+        assert_eq!(found, expected, "seed {seed}");
+
+        let closest = expected.iter().map(calculate_manhattan_distance).min();
+        assert_eq!(
+            Day03::part1(&wires).ok().map(|crossing| crossing.value),
+            closest,
+            "seed {seed}"
+        );
+        let fewest = expected
+            .iter()
+            .map(|point| {
+                first_steps(&paths[0], point).unwrap() + first_steps(&paths[1], point).unwrap()
+            })
+            .min();
+        assert_eq!(
+            Day03::part2(&wires).ok().map(|crossing| crossing.value),
+            fewest,
+            "seed {seed}"
+        );
+    }
+}
+
+#[test]
+fn long_moves_take_no_extra_memory() {
+    let wires = parse_wires("R1000000000,U5,L999999990\nU2,R2000000000").unwrap();
+    let segments = trace_segments(&wires[0]).unwrap();
+    assert_eq!(segments.len(), 3);
+    assert_eq!(segments[2].steps, 1_000_000_005);
+
+    let closest = Day03::part1(&wires).unwrap();
+    assert_eq!(
+        (closest.value, closest.point),
+        (1_000_000_002, Point::new(1_000_000_000, 2))
+    );
+    assert_eq!(Day03::part2(&wires).unwrap().value, 2_000_000_004);
+}
+
+#[test]
+fn the_port_only_counts_when_both_wires_return_to_it() {
+    let wires = parse_wires("R2,L2,L1\nU3,D3,L1").unwrap();
+    let crossing = Day03::part1(&wires).unwrap();
+    assert_eq!((crossing.value, crossing.point), (0, Point::new(0, 0)));
+    assert_eq!(Day03::part2(&wires).unwrap().value, 4 + 6);
+
+    let wires = parse_wires("R2,L2\nU3,L1").unwrap();
+    assert!(Day03::part1(&wires).is_err());
+}
+
+#[test]
+fn far_crossings_do_not_overflow() {
+    let wires = parse_wires("L2000000000,D2000000000\nD2000000000,L2000000000").unwrap();
+    let far = Point::new(-2_000_000_000, -2_000_000_000);
+    assert_eq!(calculate_manhattan_distance(&far), 4_000_000_000);
+    let closest = Day03::part1(&wires).unwrap();
+    assert_eq!((closest.value, closest.point), (4_000_000_000, far));
+    assert_eq!(Day03::part2(&wires).unwrap().value, 8_000_000_000);
+
+    // Running along each other across the port's row, for more cells than
+    // an `i32` counts.
+    let wires = parse_wires("R1,D2000000000,U2147483647\nD1,R1,D1999999999,U2147483647").unwrap();
+    let closest = Day03::part1(&wires).unwrap();
+    assert_eq!((closest.value, closest.point), (1, Point::new(1, 0)));
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use aoc_common::Solution;
+use common::{random_diagonal_wires, random_wires, step_path};
+use day03::wire::{parse_wires, Delay, Loop, Wire};
+use day03::{Day03, Point, ORIGIN};
+use std::collections::HashMap;
+
+fn wire(input: &str) -> Wire {
+    Wire::trace(&parse_wires(input).unwrap()[0]).unwrap()
+}
+
+#[test]
+fn the_port_is_only_visited_by_coming_back() {
+    let out = wire("R2");
+    assert_eq!(out.first_visit(ORIGIN), None);
+    assert_eq!(out.first_visit(Point::new(1, 0)), Some(1));
+    assert_eq!(out.first_visit(Point::new(2, 0)), Some(2));
+    assert_eq!(out.loops(), []);
+
+    let back = wire("R2,L2");
+    assert_eq!(back.visits(ORIGIN), [4]);
+    assert_eq!(back.delay(ORIGIN, Delay::First), Some(4));
+    // The signal never has to leave.
+    assert_eq!(back.delay(ORIGIN, Delay::LoopRemoved), Some(0));
+    assert_eq!(
+        back.loops(),
+        [
+            Loop {
+                point: Point::new(1, 0),
+                from: 1,
+                to: 3
+            },
+            Loop {
+                point: ORIGIN,
+                from: 0,
+                to: 4
+            },
+        ]
+    );
+}
+
+#[test]
+fn delays_count_the_chosen_visit() {
+    // Around a square and back across the second step.
+    let wire = wire("R3,U1,L1,D2");
+    assert_eq!(wire.len(), 7);
+    let crossing = Point::new(2, 0);
+    assert_eq!(wire.visits(crossing), [2, 6]);
+    assert_eq!(wire.delay(crossing, Delay::First), Some(2));
+    assert_eq!(wire.delay(crossing, Delay::Last), Some(6));
+    assert_eq!(wire.delay(crossing, Delay::LoopRemoved), Some(2));
+
+    let after = Point::new(2, -1);
+    assert_eq!(wire.delay(after, Delay::First), Some(7));
+    assert_eq!(wire.delay(after, Delay::LoopRemoved), Some(3));
+    assert_eq!(wire.delay(Point::new(5, 5), Delay::Last), None);
+
+    let crossings = wire.self_crossings();
+    assert_eq!(crossings.len(), 1);
+    assert_eq!((crossings[0].from, crossings[0].wires), (crossing, (0, 3)));
+    assert_eq!(wire.loops().iter().map(Loop::len).collect::<Vec<_>>(), [4]);
+}
+
+#[test]
+fn visits_and_loops_match_the_unit_step_path() {
+    let wires = random_wires(7, 300)
+        .into_iter()
+        .chain(random_diagonal_wires(7, 300));
+    for (seed, instructions) in wires.enumerate() {
+        let wire = Wire::trace(&instructions).unwrap();
+        let path = step_path(&instructions);
+
+        // Loop-removed delays: a signal jumps to the best delay it had on
+        // a cell whenever the wire comes back there.
+        let mut best = HashMap::from([(ORIGIN, 0)]);
+        let mut last = HashMap::from([(ORIGIN, 0)]);
+        let mut loops = Vec::new();
+        let mut delay = 0;
+        let mut visits: HashMap<Point, Vec<(usize, usize)>> = HashMap::new();
+        for (index, &point) in path.iter().enumerate() {
+            let steps = index + 1;
+            delay = (delay + 1).min(*best.get(&point).unwrap_or(&usize::MAX));
+            best.insert(point, delay);
+            if let Some(from) = last.insert(point, steps) {
+                loops.push(Loop {
+                    point,
+                    from,
+                    to: steps,
+                });
+            }
+            visits.entry(point).or_default().push((steps, delay));
+        }
+        assert_eq!(wire.loops(), loops, "wire {seed}");
+
+        for (point, visits) in visits {
+            let steps: Vec<usize> = visits.iter().map(|&(steps, _)| steps).collect();
+            assert_eq!(wire.visits(point), steps, "wire {seed} at {point:?}");
+            let fastest = visits.iter().map(|&(_, delay)| delay).min();
+            assert_eq!(
+                wire.delay(point, Delay::LoopRemoved),
+                fastest,
+                "wire {seed} at {point:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn long_moves_doubling_back_are_worked_out_stretch_by_stretch() {
+    // Ten million cells revisited, listing them would take gigabytes.
+    let out = wire("R10000000,L10000000,U1");
+    assert_eq!(out.visits(Point::new(5, 0)), [5, 19_999_995]);
+    assert_eq!(out.delay(Point::new(5, 0), Delay::Last), Some(19_999_995));
+    assert_eq!(out.delay(Point::new(5, 0), Delay::LoopRemoved), Some(5));
+    assert_eq!(out.delay(Point::new(0, 1), Delay::First), Some(20_000_001));
+    assert_eq!(out.delay(Point::new(0, 1), Delay::LoopRemoved), Some(1));
+
+    let wires = parse_wires("R10000000,L10000000,U1\nU1,R5").unwrap();
+    assert_eq!(Day03::part1(&wires).unwrap().value, 1);
+    assert_eq!(Day03::part2(&wires).unwrap().value, 20_000_002);
+}
@@ -0,0 +1,192 @@
+mod common;
+
+use common::{random_diagonal_wires, random_wires, step_path};
+use day03::wire::{parse_wires, Delay};
+use day03::wire_set::{Meeting, WireSet};
+use day03::{Point, ORIGIN};
+use std::collections::{HashMap, HashSet};
+
+/// The first example with a third wire along the `x` axis and up through
+/// the crossing at 3,3.
+const THREE_WIRES: &str = "R8,U5,L5,D3\nU7,R6,D4,L4\nR3,U4,L10\n";
+
+fn wire_set(input: &str) -> WireSet {
+    WireSet::trace(&parse_wires(input).unwrap()).unwrap()
+}
+
+#[test]
+fn crossings_are_found_between_every_pair() {
+    let wires = wire_set(THREE_WIRES);
+    let pairs: Vec<(usize, usize)> = wires
+        .pairwise_crossings()
+        .iter()
+        .map(|overlap| overlap.wires)
+        .collect();
+    for pair in [(0, 1), (0, 2), (1, 2)] {
+        assert!(pairs.contains(&pair), "{pair:?}");
+    }
+    assert_eq!(wires.crossings(1, 0).len(), 2);
+    assert!(wires
+        .crossings(2, 0)
+        .iter()
+        .all(|overlap| overlap.wires == (0, 2)));
+}
+
+#[test]
+fn meetings_list_the_cells_k_wires_pass_through() {
+    let wires = wire_set(THREE_WIRES);
+    let all = wires.meetings(3).unwrap();
+    assert_eq!(
+        all,
+        [Meeting {
+            wires: vec![0, 1, 2],
+            from: Point::new(3, 3),
+            to: Point::new(3, 3),
+        }]
+    );
+    assert_eq!(
+        wires
+            .steps_to(all[0].from)
+            .values()
+            .copied()
+            .collect::<Vec<_>>(),
+        [20, 20, 6]
+    );
+
+    let shared = wires.meetings(2).unwrap();
+    assert!(shared.len() > all.len());
+    // Closest first, the third wire runs along the first one from the port.
+    assert_eq!(shared[0].wires, [0, 2]);
+    assert_eq!(
+        shared[0].points().collect::<Vec<_>>(),
+        [Point::new(1, 0), Point::new(2, 0), Point::new(3, 0)]
+    );
+    // The cell all three pass through is left out of the pairs' meetings.
+    let around: Vec<&Meeting> = shared
+        .iter()
+        .filter(|meeting| meeting.points().any(|point| point.x == 3 && point.y >= 2))
+        .collect();
+    assert_eq!(around.len(), 3, "{around:?}");
+
+    assert!(wires.meetings(1).is_err());
+    assert!(wires.meetings(4).unwrap().is_empty());
+}
+
+#[test]
+fn meetings_match_the_cells_wires_step_through() {
+    for seed in 0..200 {
+        let wires = if seed % 2 == 0 {
+            random_wires(seed, 4)
+        } else {
+            random_diagonal_wires(seed, 4)
+        };
+        let set = WireSet::trace(&wires).unwrap();
+        let paths: Vec<HashSet<Point>> = wires
+            .iter()
+            .map(|wire| step_path(wire).into_iter().collect())
+            .collect();
+        for k in 2..=4 {
+            let mut expected: HashMap<Point, Vec<usize>> = HashMap::new();
+            for (wire, path) in paths.iter().enumerate() {
+                for &point in path {
+                    expected.entry(point).or_default().push(wire);
+                }
+            }
+            expected.retain(|_, wires| wires.len() >= k);
+
+            let meetings = set.meetings(k).unwrap();
+            let mut found: HashMap<Point, Vec<usize>> = HashMap::new();
+            for meeting in &meetings {
+                for point in meeting.points() {
+                    let twice = found.insert(point, meeting.wires.clone());
+                    assert_eq!(twice, None, "seed {seed}, k {k}: {point:?} twice");
+                }
+            }
+            assert_eq!(found, expected, "seed {seed}, k {k}");
+
+            let closest: Vec<u64> = meetings
+                .iter()
+                .map(|meeting| {
+                    meeting
+                        .points()
+                        .map(|point| {
+                            ORIGIN.x.abs_diff(point.x) as u64 + ORIGIN.y.abs_diff(point.y) as u64
+                        })
+                        .min()
+                        .unwrap()
+                })
+                .collect();
+            assert!(closest.is_sorted(), "seed {seed}, k {k}: {closest:?}");
+        }
+    }
+}
+
+#[test]
+fn long_meetings_stay_ranges() {
+    let wires = wire_set("R100000000\nU1,R100000000,D2\nR50000000");
+    let meetings = wires.meetings(2).unwrap();
+    let summary: Vec<(Vec<usize>, Point, Point)> = meetings
+        .iter()
+        .map(|meeting| (meeting.wires.clone(), meeting.from, meeting.to))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (vec![0, 2], Point::new(1, 0), Point::new(50000000, 0)),
+            (
+                vec![0, 1],
+                Point::new(100000000, 0),
+                Point::new(100000000, 0)
+            ),
+        ]
+    );
+    assert_eq!(meetings[0].len(), 50000000);
+}
+
+#[test]
+fn any_group_of_wires_can_be_compared() {
+    let wires = wire_set(THREE_WIRES);
+    let closest = wires.closest_crossing(&[0, 1]).unwrap().unwrap();
+    assert_eq!((closest.value, closest.point), (6, Point::new(3, 3)));
+    let fewest = wires
+        .fewest_combined_steps(&[0, 1], Delay::First)
+        .unwrap()
+        .unwrap();
+    assert_eq!((fewest.value, fewest.point), (30, Point::new(6, 5)));
+
+    assert_eq!(
+        wires
+            .fewest_combined_steps(&[2, 0], Delay::First)
+            .unwrap()
+            .unwrap()
+            .value,
+        2
+    );
+    let all = wires
+        .fewest_combined_steps(&[0, 1, 2], Delay::First)
+        .unwrap()
+        .unwrap();
+    assert_eq!((all.value, all.point), (46, Point::new(3, 3)));
+
+    let apart = wire_set("R2\nL2\nU1,R1");
+    assert_eq!(
+        apart.fewest_combined_steps(&[0, 1], Delay::First).unwrap(),
+        None
+    );
+    assert_eq!(apart.closest_crossing(&[0, 1, 2]).unwrap(), None);
+}
+
+#[test]
+fn groups_must_name_different_existing_wires() {
+    let wires = wire_set(THREE_WIRES);
+    for (group, error) in [
+        (&[0][..], "Pick at least two wires"),
+        (&[0, 3], "There is no wire 3, only 3"),
+        (&[1, 2, 1], "Wire 1 is picked twice"),
+    ] {
+        let err = wires
+            .fewest_combined_steps(group, Delay::First)
+            .unwrap_err();
+        assert_eq!(err.to_string(), error);
+    }
+}
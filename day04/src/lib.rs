@@ -0,0 +1,81 @@
+use anyhow::Result;
+use aoc_common::parse::{from_str, range, Parser};
+use aoc_common::Solution;
+use std::collections::HashSet;
+
+/*
+https://adventofcode.com/2019/day/04
+*/
+
+pub fn contains_duplicates(code: &str) -> bool {
+    let set: HashSet<char> = code.chars().collect();
+    set.len() != 6
+}
+
+pub fn left_right_increase_value(code: &str) -> bool {
+    code.chars().is_sorted()
+}
+
+pub fn contains_adjacent_duplicates(code: &str) -> bool {
+    let chars: Vec<char> = code.chars().collect();
+    for i in 0..chars.len() - 1 {
+        if chars[i] == chars[i + 1] {
+            return true;
+        }
+    }
+    false
+}
+
+/// Like `contains_adjacent_duplicates`, but the pair must not be part of a
+/// larger group of matching digits.
+pub fn contains_adjacent_pair(code: &str) -> bool {
+    let chars: Vec<char> = code.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() - 1 {
+        let current_char = chars[i];
+        let mut count = 1;
+
+        while i + 1 < chars.len() && chars[i + 1] == current_char {
+            count += 1;
+            i += 1;
+        }
+
+        if count == 2 {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+fn count_valid_codes(lower: u32, upper: u32, adjacent: fn(&str) -> bool) -> usize {
+    (lower..upper)
+        .map(|code| code.to_string())
+        .filter(|code| {
+            contains_duplicates(code) && left_right_increase_value(code) && adjacent(code)
+        })
+        .count()
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Input = (u32, u32);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(range(from_str()).parse(input)?.into_inner())
+    }
+
+    fn part1(&(lower, upper): &Self::Input) -> Result<usize> {
+        Ok(count_valid_codes(lower, upper, contains_adjacent_duplicates))
+    }
+
+    fn part2(&(lower, upper): &Self::Input) -> Result<usize> {
+        Ok(count_valid_codes(lower, upper, contains_adjacent_pair))
+    }
+}
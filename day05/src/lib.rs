@@ -0,0 +1,365 @@
+use anyhow::{anyhow, Result};
+use aoc_common::{parse, Answer, Solution, Value};
+use std::fmt;
+
+/*
+https://adventofcode.com/2019/day/05
+*/
+
+/// System ID of the ship's air conditioner unit (part 1).
+pub const AIR_CONDITIONER: i32 = 1;
+/// System ID of the thermal radiator controller (part 2).
+pub const THERMAL_RADIATOR: i32 = 5;
+
+#[derive(PartialEq)]
+struct OPCode {
+    identifier: u32,
+    clear_name: &'static str,
+    cycles: u8,
+}
+
+struct Instruction {
+    op_code: OPCode,
+    third_parameter_mode: u32,
+    second_parameter_mode: u32,
+    first_parameter_mode: u32,
+}
+
+const NOOP: OPCode = OPCode {
+    identifier: 0,
+    clear_name: "NOOP",
+    cycles: 1,
+};
+
+const ADD: OPCode = OPCode {
+    identifier: 1,
+    clear_name: "ADD",
+    cycles: 4,
+};
+
+const MULTIPLY: OPCode = OPCode {
+    identifier: 2,
+    clear_name: "MULTIPLY",
+    cycles: 4,
+};
+
+const INPUT: OPCode = OPCode {
+    identifier: 3,
+    clear_name: "INPUT",
+    cycles: 2,
+};
+
+const OUTPUT: OPCode = OPCode {
+    identifier: 4,
+    clear_name: "OUTPUT",
+    cycles: 2,
+};
+
+const JUMP_IF_TRUE: OPCode = OPCode {
+    identifier: 5,
+    clear_name: "JUMP_IF_TRUE",
+    cycles: 3,
+};
+
+const JUMP_IF_FALSE: OPCode = OPCode {
+    identifier: 6,
+    clear_name: "JUMP_IF_FALSE",
+    cycles: 3,
+};
+
+const LESS_THAN: OPCode = OPCode {
+    identifier: 7,
+    clear_name: "LESS_THAN",
+    cycles: 4,
+};
+
+const EQUALS: OPCode = OPCode {
+    identifier: 8,
+    clear_name: "EQUALS",
+    cycles: 4,
+};
+
+const HALT: OPCode = OPCode {
+    identifier: 99,
+    clear_name: "HALT",
+    cycles: 1,
+};
+
+fn split_into_digits(n: u32) -> Vec<u32> {
+    let mut digits: Vec<u32> = n
+        .to_string()
+        .chars()
+        .map(|c| c.to_digit(10).unwrap())
+        .collect();
+
+    if digits.len() < 5 {
+        for _ in 1..=5 - digits.len() {
+            digits.insert(0, 0);
+        }
+    }
+    digits
+}
+
+/*
+ABCDE
+01002
+
+DE - two-digit opcode,      02 == opcode 2
+ C - mode of 1st parameter,  0 == position mode
+ B - mode of 2nd parameter,  1 == immediate mode
+ A - mode of 3rd parameter,  0 == position mode
+*/
+fn get_instruction_by_id(id: u32) -> Instruction {
+    let digits = split_into_digits(id);
+    assert_eq!(digits.len(), 5, "digits.len() < 5");
+    let a = digits[0];
+    let b = digits[1];
+    let c = digits[2];
+    let de = digits[3] * 10 + digits[4];
+    Instruction {
+        op_code: {
+            match de {
+                1 => ADD,
+                2 => MULTIPLY,
+                3 => INPUT,
+                4 => OUTPUT,
+                5 => JUMP_IF_TRUE,
+                6 => JUMP_IF_FALSE,
+                7 => LESS_THAN,
+                8 => EQUALS,
+                99 => HALT,
+                _ => NOOP,
+            }
+        },
+        third_parameter_mode: a,
+        second_parameter_mode: b,
+        first_parameter_mode: c,
+    }
+}
+
+/// Runs the program feeding `input_value` to every INPUT and returns all
+/// values it OUTPUTs, in order.
+pub fn run_program(mut source_code: Vec<i32>, input_value: i32) -> Vec<i32> {
+    let mut pc = 0;
+    let mut outputs = Vec::new();
+    while pc < source_code.len() {
+        let id = source_code[pc];
+        let mut pc_increment = true;
+        assert!(id > 0);
+        let instruction = get_instruction_by_id(id as u32);
+
+        match instruction.op_code {
+            ADD => {
+                let source1 = source_code[pc + 1];
+                let source2 = source_code[pc + 2];
+                let destination = source_code[pc + 3];
+                assert_eq!(instruction.third_parameter_mode, 0);
+
+                let lhs = if instruction.first_parameter_mode == 1 {
+                    source1
+                } else {
+                    assert!(source1 > 0);
+                    source_code[source1 as usize]
+                };
+                let rhs = if instruction.second_parameter_mode == 1 {
+                    source2
+                } else {
+                    assert!(source2 >= 0);
+                    source_code[source2 as usize]
+                };
+                assert!(destination >= 0);
+                source_code[destination as usize] = lhs + rhs;
+            }
+            MULTIPLY => {
+                let source1 = source_code[pc + 1];
+                let source2 = source_code[pc + 2];
+                let destination = source_code[pc + 3];
+                assert_eq!(instruction.third_parameter_mode, 0);
+
+                let lhs = if instruction.first_parameter_mode == 1 {
+                    source1
+                } else {
+                    assert!(source1 >= 0);
+                    source_code[source1 as usize]
+                };
+                let rhs = if instruction.second_parameter_mode == 1 {
+                    source2
+                } else {
+                    assert!(source2 >= 0);
+                    source_code[source2 as usize]
+                };
+                assert!(destination >= 0);
+                source_code[destination as usize] = lhs * rhs;
+            }
+            INPUT => {
+                let destination = source_code[pc + 1] as usize;
+                source_code[destination] = input_value;
+            }
+            OUTPUT => {
+                let source = source_code[pc + 1];
+                let output_value = if instruction.first_parameter_mode == 1 {
+                    source
+                } else {
+                    assert!(source >= 0);
+                    source_code[source as usize]
+                };
+                outputs.push(output_value);
+            }
+            JUMP_IF_TRUE => {
+                let source = source_code[pc + 1];
+                let destination = source_code[pc + 2];
+
+                let lhs = if instruction.first_parameter_mode == 1 {
+                    source
+                } else {
+                    assert!(source >= 0);
+                    source_code[source as usize]
+                };
+                let rhs = if instruction.second_parameter_mode == 1 {
+                    destination
+                } else {
+                    assert!(destination >= 0);
+                    source_code[destination as usize]
+                };
+
+                if lhs != 0 {
+                    assert!(rhs >= 0);
+                    pc = rhs as usize;
+                    pc_increment = false;
+                }
+            }
+            JUMP_IF_FALSE => {
+                let source = source_code[pc + 1];
+                let destination = source_code[pc + 2];
+
+                let lhs = if instruction.first_parameter_mode == 1 {
+                    source
+                } else {
+                    assert!(source >= 0);
+                    source_code[source as usize]
+                };
+                let rhs = if instruction.second_parameter_mode == 1 {
+                    destination
+                } else {
+                    assert!(destination >= 0);
+                    source_code[destination as usize]
+                };
+
+                if lhs == 0 {
+                    assert!(rhs >= 0);
+                    pc = rhs as usize;
+                    pc_increment = false;
+                }
+            }
+            LESS_THAN => {
+                let source1 = source_code[pc + 1];
+                let source2 = source_code[pc + 2];
+                let destination = source_code[pc + 3];
+                assert_eq!(instruction.third_parameter_mode, 0);
+
+                let lhs = if instruction.first_parameter_mode == 1 {
+                    source1
+                } else {
+                    assert!(source1 >= 0);
+                    source_code[source1 as usize]
+                };
+                let rhs = if instruction.second_parameter_mode == 1 {
+                    source2
+                } else {
+                    assert!(source2 >= 0);
+                    source_code[source2 as usize]
+                };
+
+                if lhs < rhs {
+                    source_code[destination as usize] = 1;
+                } else {
+                    source_code[destination as usize] = 0;
+                }
+            }
+            EQUALS => {
+                let source1 = source_code[pc + 1];
+                let source2 = source_code[pc + 2];
+                let destination = source_code[pc + 3];
+                assert_eq!(instruction.third_parameter_mode, 0);
+
+                let lhs = if instruction.first_parameter_mode == 1 {
+                    source1
+                } else {
+                    assert!(source1 >= 0);
+                    source_code[source1 as usize]
+                };
+                let rhs = if instruction.second_parameter_mode == 1 {
+                    source2
+                } else {
+                    assert!(source2 >= 0);
+                    source_code[source2 as usize]
+                };
+
+                if lhs == rhs {
+                    source_code[destination as usize] = 1;
+                } else {
+                    source_code[destination as usize] = 0;
+                }
+            }
+            HALT => {
+                break;
+            }
+            _ => panic!("Unknown INSTRUCTION"),
+        }
+        if pc_increment {
+            pc += instruction.op_code.cycles as usize;
+        }
+    }
+    outputs
+}
+
+/// The diagnostic code is the last output, everything before it are the
+/// test results (all 0 when the system works).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiagnosticReport {
+    pub code: i32,
+    pub tests: Vec<i32>,
+}
+
+impl fmt::Display for DiagnosticReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.code)
+    }
+}
+
+impl Answer for DiagnosticReport {
+    fn diagnostics(&self) -> Vec<(&'static str, Value)> {
+        let tests = self.tests.iter().map(|&test| test.into()).collect();
+        vec![("tests", Value::Numbers(tests))]
+    }
+}
+
+fn diagnostic_code(program: &[i32], system_id: i32) -> Result<DiagnosticReport> {
+    let mut tests = run_program(program.to_vec(), system_id);
+    let code = tests
+        .pop()
+        .ok_or_else(|| anyhow!("The program produced no output"))?;
+    Ok(DiagnosticReport { code, tests })
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Input = Vec<i32>;
+    type Answer1 = DiagnosticReport;
+    type Answer2 = DiagnosticReport;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::comma_separated(input)?)
+    }
+
+    fn part1(program: &Self::Input) -> Result<DiagnosticReport> {
+        diagnostic_code(program, AIR_CONDITIONER)
+    }
+
+    fn part2(program: &Self::Input) -> Result<DiagnosticReport> {
+        diagnostic_code(program, THERMAL_RADIATOR)
+    }
+}
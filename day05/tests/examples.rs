@@ -0,0 +1 @@
+aoc_common::example_tests!(day05::Day05);
@@ -0,0 +1,155 @@
+use anyhow::{anyhow, Result};
+use aoc_common::parse::{lines_of, pair, word, Parser};
+use aoc_common::Solution;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/*
+https://adventofcode.com/2019/day/06
+*/
+
+#[derive(Debug, Clone)]
+struct Node {
+    value: String,
+    left: Option<TreeNodeRef>,
+    right: Option<TreeNodeRef>,
+}
+
+type TreeNodeRef = Rc<RefCell<Node>>;
+
+/// `(center, object)`: `object` orbits `center`.
+pub type Orbit = (String, String);
+
+fn build_tree(orbits: &[Orbit]) -> Option<TreeNodeRef> {
+    let mut nodes: HashMap<String, TreeNodeRef> = HashMap::new();
+    let mut parent_map: HashMap<String, String> = HashMap::new();
+    let mut children_set: HashSet<String> = HashSet::new();
+
+    // First pass: create all nodes and store them in the HashMap
+    for (parent, child) in orbits {
+        let parent = parent.clone();
+        let child = child.clone();
+
+        parent_map.insert(child.clone(), parent.clone());
+        children_set.insert(child.clone());
+
+        nodes.entry(parent.clone()).or_insert_with(|| {
+            Rc::new(RefCell::new(Node {
+                value: parent.clone(),
+                left: None,
+                right: None,
+            }))
+        });
+        nodes.entry(child.clone()).or_insert_with(|| {
+            Rc::new(RefCell::new(Node {
+                value: child.clone(),
+                left: None,
+                right: None,
+            }))
+        });
+    }
+
+    // Second pass: link child nodes to their respective parents
+    for (child, parent) in &parent_map {
+        if let Some(parent_node) = nodes.get(parent) {
+            if let Some(child_node) = nodes.get(child) {
+                if parent_node.borrow().left.is_none() {
+                    parent_node.borrow_mut().left = Some(Rc::clone(child_node));
+                } else {
+                    parent_node.borrow_mut().right = Some(Rc::clone(child_node));
+                }
+            }
+        }
+    }
+
+    // Find the root node (the node that is not a child of any other node)
+    let root_value = nodes
+        .keys()
+        .find(|&k| !children_set.contains(k))?
+        .clone();
+    nodes.remove(&root_value)
+}
+
+fn calculate_distances(node: &TreeNodeRef, depth: usize, distances: &mut HashMap<String, usize>) {
+    let node_borrow = node.borrow();
+    distances.insert(node_borrow.value.clone(), depth);
+
+    if let Some(ref left) = node_borrow.left {
+        calculate_distances(left, depth + 1, distances);
+    }
+    if let Some(ref right) = node_borrow.right {
+        calculate_distances(right, depth + 1, distances);
+    }
+}
+
+pub fn find_orbit_path(orbits: &[Orbit]) -> Option<usize> {
+    let mut orbit_map: HashMap<String, Vec<String>> = HashMap::new();
+
+    for (parent, child) in orbits {
+        let parent = parent.clone();
+        let child = child.clone();
+
+        orbit_map.entry(parent.clone()).or_default().push(child.clone());
+        orbit_map.entry(child).or_default().push(parent);
+    }
+
+    // Find objects that YOU and SAN are orbiting
+    let orbited_by = |object: &str| {
+        orbits
+            .iter()
+            .find(|(_, child)| child == object)
+            .map(|(parent, _)| parent.as_str())
+    };
+    let you_orbit = orbited_by("YOU")?;
+    let san_orbit = orbited_by("SAN")?;
+
+    // BFS to find shortest path
+    let mut visited = HashSet::new();
+    let mut queue = vec![(you_orbit.to_string(), 0)];
+    visited.insert(you_orbit.to_string());
+
+    while let Some((current, distance)) = queue.pop() {
+        if current == san_orbit {
+            return Some(distance);
+        }
+
+        if let Some(neighbors) = orbit_map.get(&current) {
+            for next in neighbors {
+                if !visited.contains(next) {
+                    visited.insert(next.clone());
+                    queue.push((next.clone(), distance + 1));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+
+    type Input = Vec<Orbit>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    /// Every line must be `A)B`.
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(lines_of(pair(")", word(), word())).parse(input)?)
+    }
+
+    fn part1(orbits: &Self::Input) -> Result<usize> {
+        let tree = build_tree(orbits).ok_or_else(|| anyhow!("No center of mass found"))?;
+        let mut distances = HashMap::new();
+        calculate_distances(&tree, 0, &mut distances);
+
+        Ok(distances.values().sum())
+    }
+
+    fn part2(orbits: &Self::Input) -> Result<usize> {
+        find_orbit_path(orbits).ok_or_else(|| anyhow!("No path found!"))
+    }
+}
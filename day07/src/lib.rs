@@ -0,0 +1,221 @@
+use anyhow::{bail, Result};
+use aoc_common::{parse, Answer, Solution, Value};
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::future::try_join_all;
+use intcode::{Session, Vm, VmError};
+use std::fmt;
+
+/*
+https://adventofcode.com/2019/day/07
+*/
+
+pub type Phases = std::ops::Range<i64>;
+
+#[derive(Clone, Debug)]
+pub struct Amp {
+    pub phase_setting: i64,
+}
+
+#[derive(Clone, Debug)]
+pub struct AmplificationCircuit {
+    pub stages: Vec<Amp>,
+}
+
+impl AmplificationCircuit {
+    /// One amplifier per phase setting, starting at the first permutation.
+    /// A circuit needs at least one amplifier.
+    pub fn new(phases: Phases) -> Result<Self> {
+        if phases.is_empty() {
+            bail!("No phase settings in {phases:?}, a circuit needs at least one amplifier");
+        }
+        Ok(AmplificationCircuit {
+            stages: phases.map(|phase_setting| Amp { phase_setting }).collect(),
+        })
+    }
+
+    // Generate next permutation of phase settings
+    pub fn next_permutation(&mut self) -> bool {
+        let n = self.stages.len();
+        let mut i = n - 1;
+
+        // Find the largest index i such that stages[i-1] < stages[i]
+        while i > 0 && self.stages[i - 1].phase_setting >= self.stages[i].phase_setting {
+            i -= 1;
+        }
+
+        // If no such index exists, we're at the last permutation
+        if i == 0 {
+            return false;
+        }
+
+        // Find the largest index j such that stages[j] > stages[i-1]
+        let mut j = n - 1;
+        while j >= i && self.stages[j].phase_setting <= self.stages[i - 1].phase_setting {
+            j -= 1;
+        }
+
+        // Swap the elements at i-1 and j
+        self.stages.swap(i - 1, j);
+
+        // Reverse the sequence from i to the end
+        self.stages[i..].reverse();
+
+        true
+    }
+
+    /*
+    Every amplifier runs as its own task, wired up with channels:
+
+        0 -> A -> B -> C -> D -> E -> thrusters
+             ^                   |
+             +-------------------+  (feedback)
+
+    Each channel is primed with the phase setting of the amp reading it. With
+    `feedback` E's output goes back into A, and the last value left in that
+    channel once everybody halted is the thruster signal.
+    */
+    pub fn thruster_signal(&self, program: &[i64], feedback: bool) -> Result<i64, VmError> {
+        let (signal, _) = self.run(program, feedback, false)?;
+        Ok(signal)
+    }
+
+    /// Re-runs the circuit and returns the recorded session of every amp.
+    pub fn record(&self, program: &[i64], feedback: bool) -> Result<Vec<Session>, VmError> {
+        let (_, mut vms) = self.run(program, feedback, true)?;
+        Ok(vms.iter_mut().filter_map(Vm::take_session).collect())
+    }
+
+    fn run(
+        &self,
+        program: &[i64],
+        feedback: bool,
+        record: bool,
+    ) -> Result<(i64, Vec<Vm>), VmError> {
+        let n = self.stages.len();
+        let (senders, mut receivers): (Vec<_>, Vec<_>) =
+            (0..n).map(|_| mpsc::unbounded::<i64>()).unzip();
+        let (thruster_sender, mut thruster_receiver) = mpsc::unbounded::<i64>();
+
+        for (sender, amp) in senders.iter().zip(&self.stages) {
+            sender
+                .unbounded_send(amp.phase_setting)
+                .expect("receiver is alive");
+        }
+        senders[0].unbounded_send(0).expect("receiver is alive");
+
+        // Every sender is moved into the amp writing to it, so an input
+        // ends once its writer halted and reading past it is an error
+        // rather than waiting forever. Without feedback nobody writes to A.
+        let mut senders = senders.into_iter();
+        let first = senders.next().expect("at least one amp");
+        let mut outputs: Vec<_> = senders.collect();
+        if feedback {
+            outputs.push(first);
+        } else {
+            drop(first);
+            outputs.push(thruster_sender);
+        }
+
+        let mut vms = vec![Vm::new(program.to_vec()); n];
+        if record {
+            vms.iter_mut().for_each(Vm::record);
+        }
+        let machines = vms
+            .iter_mut()
+            .zip(receivers.iter_mut())
+            .zip(outputs)
+            .map(|((vm, input), output)| vm.run_async(input, output));
+        block_on(try_join_all(machines))?;
+
+        let thrusters = if feedback {
+            &mut receivers[0]
+        } else {
+            &mut thruster_receiver
+        };
+        let mut signal = None;
+        while let Ok(value) = thrusters.try_recv() {
+            signal = Some(value);
+        }
+        Ok((signal.unwrap_or(0), vms))
+    }
+}
+
+/// Tries every permutation of `phases` and returns the highest thruster
+/// signal together with the circuit that produced it.
+pub fn best_circuit(
+    program: &[i64],
+    phases: Phases,
+    feedback: bool,
+) -> Result<(i64, AmplificationCircuit)> {
+    // Starts at the first permutation (e.g. 0,1,2,3,4)
+    let mut amp_circuit = AmplificationCircuit::new(phases)?;
+    let mut max_thruster_signal = 0;
+    let mut best = amp_circuit.clone();
+
+    loop {
+        let signal = amp_circuit.thruster_signal(program, feedback)?;
+        if signal > max_thruster_signal {
+            max_thruster_signal = signal;
+            best = amp_circuit.clone();
+        }
+
+        // Generate next permutation of phase settings
+        if !amp_circuit.next_permutation() {
+            break;
+        }
+    }
+    Ok((max_thruster_signal, best))
+}
+
+/// The highest thruster signal and the phase settings, amplifier A first,
+/// that produce it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThrusterSignal {
+    pub signal: i64,
+    pub phases: Vec<i64>,
+}
+
+impl ThrusterSignal {
+    fn best(program: &[i64], phases: Phases, feedback: bool) -> Result<Self> {
+        let (signal, circuit) = best_circuit(program, phases, feedback)?;
+        Ok(ThrusterSignal {
+            signal,
+            phases: circuit.stages.iter().map(|amp| amp.phase_setting).collect(),
+        })
+    }
+}
+
+impl fmt::Display for ThrusterSignal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.signal)
+    }
+}
+
+impl Answer for ThrusterSignal {
+    fn diagnostics(&self) -> Vec<(&'static str, Value)> {
+        vec![("phases", self.phases.clone().into())]
+    }
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+
+    type Input = Vec<i64>;
+    type Answer1 = ThrusterSignal;
+    type Answer2 = ThrusterSignal;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::comma_separated(input)?)
+    }
+
+    fn part1(program: &Self::Input) -> Result<ThrusterSignal> {
+        ThrusterSignal::best(program, 0..5, false)
+    }
+
+    fn part2(program: &Self::Input) -> Result<ThrusterSignal> {
+        ThrusterSignal::best(program, 5..10, true)
+    }
+}
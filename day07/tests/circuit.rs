@@ -0,0 +1,35 @@
+use day07::{best_circuit, AmplificationCircuit};
+use intcode::VmError;
+
+#[test]
+fn reading_past_the_last_input_is_an_error() {
+    // Reads a third value nobody sends.
+    let greedy = [3, 7, 3, 7, 3, 7, 99, 0];
+    let circuit = AmplificationCircuit::new(0..1).unwrap();
+    assert_eq!(
+        circuit.thruster_signal(&greedy, false),
+        Err(VmError::InputExhausted { pc: 4 })
+    );
+}
+
+#[test]
+fn feedback_ends_once_the_writer_halted() {
+    // Passes its signal on, then reads once more: A gets B's signal and
+    // halts, B waits for A in vain.
+    let echo_once = [3, 9, 3, 9, 4, 9, 3, 9, 99, 0];
+    let circuit = AmplificationCircuit::new(0..2).unwrap();
+    assert_eq!(
+        circuit.thruster_signal(&echo_once, true),
+        Err(VmError::InputExhausted { pc: 6 })
+    );
+}
+
+#[test]
+fn circuits_without_phases_are_rejected() {
+    #[allow(clippy::reversed_empty_ranges)]
+    for phases in [3..3, 5..0] {
+        let err = AmplificationCircuit::new(phases.clone()).unwrap_err();
+        assert!(err.to_string().starts_with("No phase settings"), "{err}");
+        assert!(best_circuit(&[99], phases, false).is_err());
+    }
+}
@@ -0,0 +1 @@
+aoc_common::example_tests!(day07::Day07);
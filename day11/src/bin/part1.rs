@@ -0,0 +1,49 @@
+use anyhow::{bail, Result};
+use aoc_common::{default_input, for_each_source, output_paths, sources};
+use day11::robot::{self, Color, Protocol};
+use day11::Day11;
+use intcode::Vm;
+
+/*
+https://adventofcode.com/2019/day/11
+
+Usage: day11-part1 [<input>... | -] [--record <file>]
+
+With several inputs the recordings are named after them, `run.txt` becomes
+`run-input.txt`.
+*/
+
+fn main() -> Result<()> {
+    let mut paths = Vec::new();
+    let mut record: Option<String> = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--record" => match args.next() {
+                Some(path) => record = Some(path),
+                None => bail!("Usage: day11-part1 [<input>... | -] [--record <file>]"),
+            },
+            _ => paths.push(arg),
+        }
+    }
+
+    let inputs = sources(paths, default_input!());
+    let recordings = match &record {
+        Some(path) => output_paths(path, &inputs)?,
+        None => Vec::new(),
+    };
+    for_each_source(&inputs, |index, source| {
+        let mut vm = Vm::new(source.parse::<Day11>()?);
+        if record.is_some() {
+            vm.record();
+        }
+
+        let hull = robot::run_vm(&mut vm, Color::Black, Protocol::Paint)?;
+
+        println!("Painted panels: {}", hull.painted_count());
+        if let (Some(path), Some(session)) = (recordings.get(index), vm.session()) {
+            session.save(&path.to_string_lossy())?;
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,70 @@
+use anyhow::{bail, Result};
+use aoc_common::{default_input, for_each_source, output_paths, sources};
+use day11::robot::{self, Color, Protocol};
+use day11::{render, Day11};
+use intcode::Vm;
+
+/*
+https://adventofcode.com/2019/day/11
+
+Usage: day11-part2 [<input>... | -] [--png <file>] [--ppm <file>] [--record <file>]
+
+With several inputs the files are named after them, `hull.png` becomes
+`hull-input.png`.
+*/
+
+const IMAGE_SCALE: usize = 10;
+
+fn main() -> Result<()> {
+    let mut paths = Vec::new();
+    let mut images: Vec<(String, String)> = Vec::new();
+    let mut record: Option<String> = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if !arg.starts_with("--") {
+            paths.push(arg);
+            continue;
+        }
+        let Some(path) = args.next() else {
+            bail!("{arg} needs a file name");
+        };
+        match arg.as_str() {
+            "--png" | "--ppm" => images.push((arg, path)),
+            "--record" => record = Some(path),
+            _ => bail!("Unknown flag {arg}"),
+        }
+    }
+
+    let inputs = sources(paths, default_input!());
+    let images = images
+        .into_iter()
+        .map(|(flag, path)| Ok((flag, output_paths(&path, &inputs)?)))
+        .collect::<Result<Vec<_>>>()?;
+    let recordings = match &record {
+        Some(path) => output_paths(path, &inputs)?,
+        None => Vec::new(),
+    };
+    for_each_source(&inputs, |index, source| {
+        let mut vm = Vm::new(source.parse::<Day11>()?);
+        if record.is_some() {
+            vm.record();
+        }
+
+        let hull = robot::run_vm(&mut vm, Color::White, Protocol::Paint)?;
+        println!("{}", render::to_terminal(&hull));
+
+        for (flag, paths) in &images {
+            let path = paths[index].to_string_lossy();
+            if flag == "--png" {
+                render::write_png(&hull, &path, IMAGE_SCALE)?;
+            } else {
+                render::write_ppm(&hull, &path, IMAGE_SCALE)?;
+            }
+        }
+        if let (Some(path), Some(session)) = (recordings.get(index), vm.session()) {
+            session.save(&path.to_string_lossy())?;
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,35 @@
+use anyhow::Result;
+use aoc_common::{parse, Solution};
+use robot::{Color, Protocol};
+
+/*
+https://adventofcode.com/2019/day/11
+*/
+
+pub mod render;
+pub mod robot;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = Vec<i64>;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::comma_separated(input)?)
+    }
+
+    fn part1(program: &Self::Input) -> Result<usize> {
+        let hull = robot::run(program.clone(), Color::Black, Protocol::Paint)?;
+        Ok(hull.painted_count())
+    }
+
+    /// The registration identifier, as painted on the hull.
+    fn part2(program: &Self::Input) -> Result<String> {
+        let hull = robot::run(program.clone(), Color::White, Protocol::Paint)?;
+        Ok(render::to_terminal(&hull))
+    }
+}
@@ -0,0 +1,79 @@
+use crate::robot::{Color, Hull};
+use anyhow::Result;
+use day03::Point;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+const WHITE: [u8; 3] = [255, 255, 255];
+const BLACK: [u8; 3] = [0, 0, 0];
+
+/// Top row is the highest `y`, so the output reads the way the robot saw it.
+fn rows(hull: &Hull) -> Vec<Vec<Color>> {
+    let Some((min, max)) = hull.bounds() else {
+        return Vec::new();
+    };
+    (min.y..=max.y)
+        .rev()
+        .map(|y| {
+            (min.x..=max.x)
+                .map(|x| hull.color_at(&Point::new(x, y)))
+                .collect()
+        })
+        .collect()
+}
+
+pub fn to_terminal(hull: &Hull) -> String {
+    rows(hull)
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|color| match color {
+                    Color::White => '█',
+                    Color::Black => ' ',
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// RGB pixels, each panel blown up to a `scale` x `scale` square.
+fn pixels(hull: &Hull, scale: usize) -> (usize, usize, Vec<u8>) {
+    let rows = rows(hull);
+    let width = rows.first().map_or(0, |row| row.len()) * scale;
+    let height = rows.len() * scale;
+    let mut data = Vec::with_capacity(width * height * 3);
+    for row in &rows {
+        for _ in 0..scale {
+            for color in row {
+                let rgb = match color {
+                    Color::White => WHITE,
+                    Color::Black => BLACK,
+                };
+                for _ in 0..scale {
+                    data.extend_from_slice(&rgb);
+                }
+            }
+        }
+    }
+    (width, height, data)
+}
+
+pub fn write_ppm(hull: &Hull, path: &str, scale: usize) -> Result<()> {
+    let (width, height, data) = pixels(hull, scale);
+    let mut writer = BufWriter::new(File::create(path)?);
+    write!(writer, "P6\n{width} {height}\n255\n")?;
+    writer.write_all(&data)?;
+    writer.flush()?;
+    Ok(())
+}
+
+pub fn write_png(hull: &Hull, path: &str, scale: usize) -> Result<()> {
+    let (width, height, data) = pixels(hull, scale);
+    let writer = BufWriter::new(File::create(path)?);
+    let mut encoder = png::Encoder::new(writer, width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header()?.write_image_data(&data)?;
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use anyhow::{bail, Result};
+use day03::{Point, ORIGIN};
+use intcode::{State, Vm};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    White,
+}
+
+impl Color {
+    pub fn from_code(code: i64) -> Result<Self> {
+        match code {
+            0 => Ok(Color::Black),
+            1 => Ok(Color::White),
+            _ => bail!("Unknown color {code}"),
+        }
+    }
+
+    pub fn code(self) -> i64 {
+        match self {
+            Color::Black => 0,
+            Color::White => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Heading {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Heading {
+    pub fn turn_left(self) -> Self {
+        match self {
+            Heading::Up => Heading::Left,
+            Heading::Left => Heading::Down,
+            Heading::Down => Heading::Right,
+            Heading::Right => Heading::Up,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        self.turn_left().turn_left().turn_left()
+    }
+
+    pub fn step(self, point: Point) -> Point {
+        match self {
+            Heading::Up => Point::new(point.x, point.y + 1),
+            Heading::Down => Point::new(point.x, point.y - 1),
+            Heading::Left => Point::new(point.x - 1, point.y),
+            Heading::Right => Point::new(point.x + 1, point.y),
+        }
+    }
+}
+
+/*
+How the second output of each pair moves the robot after it painted.
+
+Paint: 0 == turn left, 1 == turn right, then one step forward
+Move:  0..=3 == face up, right, down, left, then one step forward
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Paint,
+    Move,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Hull {
+    panels: HashMap<Point, Color>,
+    painted: HashSet<Point>,
+}
+
+impl Hull {
+    pub fn new() -> Self {
+        Hull::default()
+    }
+
+    pub fn color_at(&self, point: &Point) -> Color {
+        self.panels.get(point).copied().unwrap_or(Color::Black)
+    }
+
+    pub fn paint(&mut self, point: Point, color: Color) {
+        self.panels.insert(point, color);
+        self.painted.insert(point);
+    }
+
+    /// Panels the robot painted at least once, no matter the color.
+    pub fn painted_count(&self) -> usize {
+        self.painted.len()
+    }
+
+    /// Lower left and upper right corner of all known panels.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        let mut points = self.panels.keys();
+        let first = *points.next()?;
+        Some(points.fold((first, first), |(min, max), p| {
+            (
+                Point::new(min.x.min(p.x), min.y.min(p.y)),
+                Point::new(max.x.max(p.x), max.y.max(p.y)),
+            )
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Robot {
+    pub position: Point,
+    pub heading: Heading,
+}
+
+impl Robot {
+    pub fn new() -> Self {
+        Robot {
+            position: ORIGIN,
+            heading: Heading::Up,
+        }
+    }
+
+    fn apply(&mut self, protocol: Protocol, movement: i64) -> Result<()> {
+        self.heading = match (protocol, movement) {
+            (Protocol::Paint, 0) => self.heading.turn_left(),
+            (Protocol::Paint, 1) => self.heading.turn_right(),
+            (Protocol::Move, 0) => Heading::Up,
+            (Protocol::Move, 1) => Heading::Right,
+            (Protocol::Move, 2) => Heading::Down,
+            (Protocol::Move, 3) => Heading::Left,
+            _ => bail!("Unknown {protocol:?} movement {movement}"),
+        };
+        self.position = self.heading.step(self.position);
+        Ok(())
+    }
+}
+
+impl Default for Robot {
+    fn default() -> Self {
+        Robot::new()
+    }
+}
+
+/// Runs `program` as the robot's brain on a hull whose starting panel has
+/// `start` color. Every input is the color below the robot, every pair of
+/// outputs is `(paint color, movement)`. Halting halfway through a pair is
+/// an error.
+pub fn run(program: Vec<i64>, start: Color, protocol: Protocol) -> Result<Hull> {
+    run_vm(&mut Vm::new(program), start, protocol)
+}
+
+/// Like `run`, but on a VM prepared by the caller (e.g. one that records
+/// its session).
+pub fn run_vm(vm: &mut Vm, start: Color, protocol: Protocol) -> Result<Hull> {
+    let mut hull = Hull::new();
+    let mut robot = Robot::new();
+    hull.panels.insert(ORIGIN, start);
+
+    let mut pending_color: Option<Color> = None;
+    loop {
+        match vm.run()? {
+            State::NeedsInput => vm.push_input(hull.color_at(&robot.position).code()),
+            State::Output(value) => match pending_color.take() {
+                None => pending_color = Some(Color::from_code(value)?),
+                Some(color) => {
+                    hull.paint(robot.position, color);
+                    robot.apply(protocol, value)?;
+                }
+            },
+            State::Halted => {
+                if let Some(color) = pending_color {
+                    bail!("The robot halted after painting {color:?} without moving");
+                }
+                break;
+            }
+        }
+    }
+    Ok(hull)
+}
@@ -0,0 +1,42 @@
+use day03::Point;
+use day11::robot::{run, Color, Protocol};
+
+#[test]
+fn the_robot_paints_then_turns_and_steps() {
+    // Paints whatever it reads, turning left both times.
+    let echo = vec![3, 100, 4, 100, 104, 0, 3, 100, 4, 100, 104, 0, 99];
+    let hull = run(echo, Color::White, Protocol::Paint).unwrap();
+    assert_eq!(hull.painted_count(), 2);
+    assert_eq!(hull.color_at(&Point::new(0, 0)), Color::White);
+    assert_eq!(hull.color_at(&Point::new(-1, 0)), Color::Black);
+
+    // Turns right three times, painting white, white and black.
+    let turns = vec![104, 1, 104, 1, 104, 1, 104, 1, 104, 0, 104, 1, 99];
+    let hull = run(turns, Color::Black, Protocol::Paint).unwrap();
+    assert_eq!(hull.painted_count(), 3);
+    assert_eq!(hull.bounds(), Some((Point::new(0, -1), Point::new(1, 0))));
+    assert_eq!(hull.color_at(&Point::new(1, -1)), Color::Black);
+}
+
+#[test]
+fn halting_between_color_and_movement_is_an_error() {
+    let err = run(vec![104, 1, 99], Color::Black, Protocol::Paint).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "The robot halted after painting White without moving"
+    );
+    assert!(run(vec![104, 1, 104, 2, 99], Color::Black, Protocol::Paint).is_err());
+}
+
+#[test]
+fn the_move_protocol_faces_the_given_heading() {
+    // White facing down, white facing right, black facing up.
+    let moves = vec![104, 1, 104, 2, 104, 1, 104, 1, 104, 0, 104, 0, 99];
+    let hull = run(moves, Color::Black, Protocol::Move).unwrap();
+    assert_eq!(hull.painted_count(), 3);
+    assert_eq!(hull.color_at(&Point::new(0, 0)), Color::White);
+    assert_eq!(hull.color_at(&Point::new(0, -1)), Color::White);
+    assert_eq!(hull.color_at(&Point::new(1, -1)), Color::Black);
+
+    assert!(run(vec![104, 1, 104, 4, 99], Color::Black, Protocol::Move).is_err());
+}
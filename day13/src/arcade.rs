@@ -0,0 +1,84 @@
+use crate::joystick::Controller;
+use crate::screen::Screen;
+use anyhow::Result;
+use intcode::{Session, State, Vm};
+
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Address the number of quarters is stored at; 2 means free play.
+const QUARTERS_ADDRESS: usize = 0;
+const FREE_PLAY: i64 = 2;
+
+pub struct Arcade<C: Controller> {
+    vm: Vm,
+    screen: Screen,
+    controller: C,
+    display: bool,
+}
+
+impl<C: Controller> Arcade<C> {
+    pub fn new(program: Vec<i64>, controller: C) -> Self {
+        Arcade {
+            vm: Vm::new(program),
+            screen: Screen::new(),
+            controller,
+            display: true,
+        }
+    }
+
+    /// Inserts quarters so the game can actually be played.
+    pub fn free_play(mut program: Vec<i64>, controller: C) -> Self {
+        if let Some(quarters) = program.get_mut(QUARTERS_ADDRESS) {
+            *quarters = FREE_PLAY;
+        }
+        Arcade::new(program, controller)
+    }
+
+    /// Never draws to the terminal, for CI and scripted runs.
+    pub fn headless(mut self) -> Self {
+        self.display = false;
+        self
+    }
+
+    /// Records the session so the game can be replayed as a regression test.
+    pub fn record(mut self) -> Self {
+        self.vm.record();
+        self
+    }
+
+    pub fn session(&self) -> Option<&Session> {
+        self.vm.session()
+    }
+
+    pub fn screen(&self) -> &Screen {
+        &self.screen
+    }
+
+    /// Runs the game to completion and returns the final score.
+    pub fn run(&mut self) -> Result<i64> {
+        let mut triple: Vec<i64> = Vec::with_capacity(3);
+        loop {
+            match self.vm.run()? {
+                State::NeedsInput => {
+                    if self.display {
+                        print!("{CLEAR_SCREEN}{}", self.screen.render());
+                    }
+                    let joystick = self.controller.joystick(&self.screen)?;
+                    self.vm.push_input(joystick.code());
+                }
+                State::Output(value) => {
+                    triple.push(value);
+                    if triple.len() == 3 {
+                        self.screen.draw(triple[0], triple[1], triple[2])?;
+                        triple.clear();
+                    }
+                }
+                State::Halted => break,
+            }
+        }
+        if self.display {
+            print!("{CLEAR_SCREEN}{}", self.screen.render());
+        }
+        Ok(self.screen.score())
+    }
+}
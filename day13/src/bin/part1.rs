@@ -0,0 +1,17 @@
+use anyhow::Result;
+use aoc_common::{default_input, for_each_input, sources, Solution};
+use day13::Day13;
+
+/*
+https://adventofcode.com/2019/day/13
+
+Usage: day13-part1 [<input>... | -]
+*/
+
+fn main() -> Result<()> {
+    let inputs = sources(std::env::args().skip(1), default_input!());
+    for_each_input::<Day13>(&inputs, |program| {
+        println!("Block tiles: {}", Day13::part1(program)?);
+        Ok(())
+    })
+}
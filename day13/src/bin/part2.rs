@@ -0,0 +1,82 @@
+use anyhow::{bail, Result};
+use aoc_common::{default_input, for_each_source, output_paths, read_input, sources};
+use day13::arcade::Arcade;
+use day13::joystick::{Controller, FollowBall, Keyboard, Scripted};
+use day13::Day13;
+
+/*
+https://adventofcode.com/2019/day/13
+
+Usage: day13-part2 [<input>...] [--controller ai|keyboard] [--script <file>] [--headless] [--record <file>]
+
+The keyboard controller reads stdin, so it can't be combined with `-`.
+With several inputs the recordings are named after them, `game.txt`
+becomes `game-input.txt`.
+*/
+
+enum Choice {
+    Ai,
+    Keyboard,
+    Script(String),
+}
+
+fn main() -> Result<()> {
+    let mut paths = Vec::new();
+    let mut choice = Choice::Ai;
+    let mut headless = false;
+    let mut record: Option<String> = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--headless" => headless = true,
+            "--controller" => match args.next().as_deref() {
+                Some("ai") => choice = Choice::Ai,
+                Some("keyboard") => choice = Choice::Keyboard,
+                other => bail!("Unknown controller {other:?}"),
+            },
+            "--script" => {
+                let Some(path) = args.next() else {
+                    bail!("--script needs a file name");
+                };
+                choice = Choice::Script(read_input(&path)?);
+            }
+            "--record" => {
+                let Some(path) = args.next() else {
+                    bail!("--record needs a file name");
+                };
+                record = Some(path);
+            }
+            flag if flag.starts_with("--") => bail!("Unknown flag {flag}"),
+            _ => paths.push(arg),
+        }
+    }
+
+    let inputs = sources(paths, default_input!());
+    let recordings = match &record {
+        Some(path) => output_paths(path, &inputs)?,
+        None => Vec::new(),
+    };
+    for_each_source(&inputs, |index, source| {
+        let program = source.parse::<Day13>()?;
+        let controller: Box<dyn Controller> = match &choice {
+            Choice::Ai => Box::new(FollowBall),
+            Choice::Keyboard => Box::new(Keyboard),
+            Choice::Script(script) => Box::new(Scripted::parse(script)?),
+        };
+        let mut arcade = Arcade::free_play(program, controller);
+        if headless {
+            arcade = arcade.headless();
+        }
+        if record.is_some() {
+            arcade = arcade.record();
+        }
+        let score = arcade.run()?;
+        if let (Some(path), Some(session)) = (recordings.get(index), arcade.session()) {
+            session.save(&path.to_string_lossy())?;
+        }
+
+        println!("Final score: {score}");
+        Ok(())
+    })
+}
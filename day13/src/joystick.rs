@@ -0,0 +1,111 @@
+use crate::screen::Screen;
+use anyhow::{bail, Result};
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Joystick {
+    Left,
+    Neutral,
+    Right,
+}
+
+impl Joystick {
+    pub fn code(self) -> i64 {
+        match self {
+            Joystick::Left => -1,
+            Joystick::Neutral => 0,
+            Joystick::Right => 1,
+        }
+    }
+
+    pub fn from_code(code: i64) -> Result<Self> {
+        match code {
+            -1 => Ok(Joystick::Left),
+            0 => Ok(Joystick::Neutral),
+            1 => Ok(Joystick::Right),
+            _ => bail!("Unknown joystick position {code}"),
+        }
+    }
+}
+
+/// Decides the joystick position whenever the game asks for input.
+pub trait Controller {
+    fn joystick(&mut self, screen: &Screen) -> Result<Joystick>;
+}
+
+/*
+Reads one line per move from stdin.
+
+a / h == left
+d / l == right
+anything else (e.g. just Enter) == neutral
+*/
+pub struct Keyboard;
+
+impl Controller for Keyboard {
+    fn joystick(&mut self, _screen: &Screen) -> Result<Joystick> {
+        let mut line = String::new();
+        if std::io::stdin().lock().read_line(&mut line)? == 0 {
+            bail!("Keyboard input closed");
+        }
+        Ok(match line.trim() {
+            "a" | "h" => Joystick::Left,
+            "d" | "l" => Joystick::Right,
+            _ => Joystick::Neutral,
+        })
+    }
+}
+
+/// Replays a fixed list of moves, then keeps the joystick neutral.
+pub struct Scripted {
+    moves: VecDeque<Joystick>,
+}
+
+impl Scripted {
+    pub fn new(moves: Vec<Joystick>) -> Self {
+        Scripted {
+            moves: moves.into(),
+        }
+    }
+
+    /// Moves as joystick codes (`-1`, `0`, `1`) separated by commas or
+    /// whitespace.
+    pub fn parse(script: &str) -> Result<Self> {
+        let moves = script
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|token| !token.is_empty())
+            .map(|token| Joystick::from_code(token.parse()?))
+            .collect::<Result<Vec<Joystick>>>()?;
+        Ok(Scripted::new(moves))
+    }
+}
+
+impl Controller for Scripted {
+    fn joystick(&mut self, _screen: &Screen) -> Result<Joystick> {
+        Ok(self.moves.pop_front().unwrap_or(Joystick::Neutral))
+    }
+}
+
+/// Keeps the paddle under the ball.
+pub struct FollowBall;
+
+impl Controller for FollowBall {
+    fn joystick(&mut self, screen: &Screen) -> Result<Joystick> {
+        let (Some(ball), Some(paddle)) = (screen.ball(), screen.paddle()) else {
+            return Ok(Joystick::Neutral);
+        };
+        Ok(match ball.x.cmp(&paddle.x) {
+            Ordering::Less => Joystick::Left,
+            Ordering::Equal => Joystick::Neutral,
+            Ordering::Greater => Joystick::Right,
+        })
+    }
+}
+
+impl<C: Controller + ?Sized> Controller for Box<C> {
+    fn joystick(&mut self, screen: &Screen) -> Result<Joystick> {
+        (**self).joystick(screen)
+    }
+}
@@ -0,0 +1,40 @@
+use anyhow::Result;
+use aoc_common::{parse, Solution};
+use arcade::Arcade;
+use joystick::{FollowBall, Scripted};
+use screen::Tile;
+
+/*
+https://adventofcode.com/2019/day/13
+*/
+
+pub mod arcade;
+pub mod joystick;
+pub mod screen;
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Input = Vec<i64>;
+    type Answer1 = usize;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(parse::comma_separated(input)?)
+    }
+
+    fn part1(program: &Self::Input) -> Result<usize> {
+        let mut arcade = Arcade::new(program.clone(), Scripted::new(Vec::new())).headless();
+        arcade.run()?;
+        Ok(arcade.screen().count(Tile::Block))
+    }
+
+    /// Final score once the follow-the-ball AI broke every block.
+    fn part2(program: &Self::Input) -> Result<i64> {
+        Arcade::free_play(program.clone(), FollowBall)
+            .headless()
+            .run()
+    }
+}
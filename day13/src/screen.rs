@@ -0,0 +1,103 @@
+use anyhow::{bail, Result};
+use day03::Point;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Empty,
+    Wall,
+    Block,
+    Paddle,
+    Ball,
+}
+
+impl Tile {
+    pub fn from_id(id: i64) -> Result<Self> {
+        match id {
+            0 => Ok(Tile::Empty),
+            1 => Ok(Tile::Wall),
+            2 => Ok(Tile::Block),
+            3 => Ok(Tile::Paddle),
+            4 => Ok(Tile::Ball),
+            _ => bail!("Unknown tile id {id}"),
+        }
+    }
+
+    fn glyph(self) -> char {
+        match self {
+            Tile::Empty => ' ',
+            Tile::Wall => '█',
+            Tile::Block => '▒',
+            Tile::Paddle => '▔',
+            Tile::Ball => '●',
+        }
+    }
+}
+
+/*
+Framebuffer filled from `(x, y, tile_id)` output triples.
+
+The triple `(-1, 0, score)` does not draw anything, it updates the score
+display instead.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct Screen {
+    tiles: HashMap<Point, Tile>,
+    score: i64,
+}
+
+const SCORE_CHANNEL: Point = Point { x: -1, y: 0 };
+
+impl Screen {
+    pub fn new() -> Self {
+        Screen::default()
+    }
+
+    pub fn draw(&mut self, x: i64, y: i64, value: i64) -> Result<()> {
+        let point = Point::new(x.try_into()?, y.try_into()?);
+        if point == SCORE_CHANNEL {
+            self.score = value;
+        } else {
+            self.tiles.insert(point, Tile::from_id(value)?);
+        }
+        Ok(())
+    }
+
+    pub fn score(&self) -> i64 {
+        self.score
+    }
+
+    pub fn count(&self, tile: Tile) -> usize {
+        self.tiles.values().filter(|&&t| t == tile).count()
+    }
+
+    fn find(&self, tile: Tile) -> Option<Point> {
+        self.tiles
+            .iter()
+            .find(|(_, &t)| t == tile)
+            .map(|(point, _)| *point)
+    }
+
+    pub fn ball(&self) -> Option<Point> {
+        self.find(Tile::Ball)
+    }
+
+    pub fn paddle(&self) -> Option<Point> {
+        self.find(Tile::Paddle)
+    }
+
+    /// Screen y grows downwards, so row 0 is printed first.
+    pub fn render(&self) -> String {
+        let max_x = self.tiles.keys().map(|p| p.x).max().unwrap_or(0);
+        let max_y = self.tiles.keys().map(|p| p.y).max().unwrap_or(0);
+        let mut out = format!("Score: {}\n", self.score);
+        for y in 0..=max_y {
+            for x in 0..=max_x {
+                let tile = self.tiles.get(&Point::new(x, y)).copied();
+                out.push(tile.unwrap_or(Tile::Empty).glyph());
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
@@ -0,0 +1,26 @@
+use day13::arcade::Arcade;
+use day13::joystick::{FollowBall, Joystick, Scripted};
+use day13::screen::Tile;
+
+/*
+Draws the ball at (1, 0) and the paddle at (0, 0), reads the joystick once and
+reports the joystick code as the score.
+*/
+const ECHO_JOYSTICK: [i64; 21] = [
+    104, 1, 104, 0, 104, 4, 104, 0, 104, 0, 104, 3, 3, 100, 104, -1, 104, 0, 4, 100, 99,
+];
+
+#[test]
+fn follow_ball_moves_towards_the_ball() {
+    let mut arcade = Arcade::new(ECHO_JOYSTICK.to_vec(), FollowBall).headless();
+    assert_eq!(arcade.run().unwrap(), Joystick::Right.code());
+    assert_eq!(arcade.screen().count(Tile::Ball), 1);
+    assert_eq!(arcade.screen().count(Tile::Paddle), 1);
+}
+
+#[test]
+fn scripted_moves_are_replayed_in_order() {
+    let script = Scripted::parse("-1, 1").unwrap();
+    let mut arcade = Arcade::new(ECHO_JOYSTICK.to_vec(), script).headless();
+    assert_eq!(arcade.run().unwrap(), Joystick::Left.code());
+}
@@ -0,0 +1,36 @@
+#![no_main]
+
+/*
+Arbitrary programs and inputs must never panic the VM, every failure has to
+come back as a `VmError`.
+
+cargo +nightly fuzz run vm
+*/
+
+use arbitrary::Arbitrary;
+use intcode::{State, Vm};
+use libfuzzer_sys::fuzz_target;
+
+const STEP_LIMIT: u64 = 100_000;
+
+#[derive(Arbitrary, Debug)]
+struct Run {
+    program: Vec<i64>,
+    inputs: Vec<i64>,
+}
+
+fuzz_target!(|run: Run| {
+    let mut vm = Vm::new(run.program);
+    vm.set_step_limit(STEP_LIMIT);
+    let mut inputs = run.inputs.into_iter();
+    loop {
+        match vm.run() {
+            Ok(State::NeedsInput) => match inputs.next() {
+                Some(value) => vm.push_input(value),
+                None => break,
+            },
+            Ok(State::Output(_)) => {}
+            Ok(State::Halted) | Err(_) => break,
+        }
+    }
+});
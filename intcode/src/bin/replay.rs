@@ -0,0 +1,26 @@
+use anyhow::{bail, Context, Result};
+use intcode::{parse_program, Session};
+use std::fs;
+
+/*
+Re-runs a program against a recorded session and fails if anything differs.
+
+Usage: replay <program> <session>
+*/
+
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [program_file, session_file] = args.as_slice() else {
+        bail!("Usage: replay <program> <session>");
+    };
+
+    let source = fs::read_to_string(program_file).with_context(|| program_file.clone())?;
+    let program = parse_program(&source).with_context(|| program_file.clone())?;
+    let session = Session::load(session_file).with_context(|| session_file.clone())?;
+    session
+        .replay(program)
+        .with_context(|| session_file.clone())?;
+
+    println!("Replay OK: {} events match", session.events().len());
+    Ok(())
+}
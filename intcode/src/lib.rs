@@ -0,0 +1,392 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+pub mod session;
+mod stream;
+
+pub use session::{Event, ReplayError, Session};
+
+/*
+Intcode interpreter shared by the hosts (day11 hull robot, ...).
+
+Unlike the copies in day05 and day07 the VM does not pull its input from a
+fixed value or print its output. `Vm::run` suspends as soon as the program
+needs an input that has not been pushed yet or produces an output, so the host
+decides what to feed it next.
+
+https://adventofcode.com/2019/day/9 (relative mode, large memory)
+*/
+
+#[derive(PartialEq, Debug)]
+struct OPCode {
+    identifier: i64,
+    clear_name: &'static str,
+    cycles: u8,
+}
+
+struct Instruction {
+    op_code: OPCode,
+    third_parameter_mode: i64,
+    second_parameter_mode: i64,
+    first_parameter_mode: i64,
+}
+
+const ADD: OPCode = OPCode {
+    identifier: 1,
+    clear_name: "ADD",
+    cycles: 4,
+};
+
+const MULTIPLY: OPCode = OPCode {
+    identifier: 2,
+    clear_name: "MULTIPLY",
+    cycles: 4,
+};
+
+const INPUT: OPCode = OPCode {
+    identifier: 3,
+    clear_name: "INPUT",
+    cycles: 2,
+};
+
+const OUTPUT: OPCode = OPCode {
+    identifier: 4,
+    clear_name: "OUTPUT",
+    cycles: 2,
+};
+
+const JUMP_IF_TRUE: OPCode = OPCode {
+    identifier: 5,
+    clear_name: "JUMP_IF_TRUE",
+    cycles: 3,
+};
+
+const JUMP_IF_FALSE: OPCode = OPCode {
+    identifier: 6,
+    clear_name: "JUMP_IF_FALSE",
+    cycles: 3,
+};
+
+const LESS_THAN: OPCode = OPCode {
+    identifier: 7,
+    clear_name: "LESS_THAN",
+    cycles: 4,
+};
+
+const EQUALS: OPCode = OPCode {
+    identifier: 8,
+    clear_name: "EQUALS",
+    cycles: 4,
+};
+
+const ADJUST_RELATIVE_BASE: OPCode = OPCode {
+    identifier: 9,
+    clear_name: "ADJUST_RELATIVE_BASE",
+    cycles: 2,
+};
+
+const HALT: OPCode = OPCode {
+    identifier: 99,
+    clear_name: "HALT",
+    cycles: 1,
+};
+
+const POSITION_MODE: i64 = 0;
+const IMMEDIATE_MODE: i64 = 1;
+const RELATIVE_MODE: i64 = 2;
+
+/// Memory grows on demand, but never beyond this many cells.
+pub const MAX_MEMORY: usize = 1 << 24;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VmError {
+    UnknownOpcode { pc: usize, opcode: i64 },
+    InvalidParameterMode { pc: usize, mode: i64 },
+    ImmediateWrite { pc: usize },
+    AddressOutOfRange { pc: usize, address: i64 },
+    Overflow { pc: usize },
+    InputExhausted { pc: usize },
+    OutputClosed { pc: usize },
+    StepLimitExceeded { pc: usize },
+}
+
+impl fmt::Display for VmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VmError::UnknownOpcode { pc, opcode } => {
+                write!(f, "unknown opcode {opcode} at pc {pc}")
+            }
+            VmError::InvalidParameterMode { pc, mode } => {
+                write!(f, "invalid parameter mode {mode} at pc {pc}")
+            }
+            VmError::ImmediateWrite { pc } => {
+                write!(f, "write parameter in immediate mode at pc {pc}")
+            }
+            VmError::AddressOutOfRange { pc, address } => {
+                write!(f, "address {address} out of range at pc {pc}")
+            }
+            VmError::Overflow { pc } => write!(f, "arithmetic overflow at pc {pc}"),
+            VmError::InputExhausted { pc } => write!(f, "no input left at pc {pc}"),
+            VmError::OutputClosed { pc } => write!(f, "output closed at pc {pc}"),
+            VmError::StepLimitExceeded { pc } => write!(f, "step limit exceeded at pc {pc}"),
+        }
+    }
+}
+
+impl std::error::Error for VmError {}
+
+/// Why `Vm::run` handed control back to the host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    NeedsInput,
+    Output(i64),
+    Halted,
+}
+
+/*
+ABCDE
+01002
+
+DE - two-digit opcode,      02 == opcode 2
+ C - mode of 1st parameter,  0 == position mode
+ B - mode of 2nd parameter,  1 == immediate mode
+ A - mode of 3rd parameter,  2 == relative mode
+*/
+fn decode(pc: usize, id: i64) -> Result<Instruction, VmError> {
+    if id < 0 {
+        return Err(VmError::UnknownOpcode { pc, opcode: id });
+    }
+    let op_code = match id % 100 {
+        1 => ADD,
+        2 => MULTIPLY,
+        3 => INPUT,
+        4 => OUTPUT,
+        5 => JUMP_IF_TRUE,
+        6 => JUMP_IF_FALSE,
+        7 => LESS_THAN,
+        8 => EQUALS,
+        9 => ADJUST_RELATIVE_BASE,
+        99 => HALT,
+        _ => return Err(VmError::UnknownOpcode { pc, opcode: id }),
+    };
+    let modes = [id / 100 % 10, id / 1000 % 10, id / 10000 % 10];
+    if let Some(&mode) = modes.iter().find(|&&mode| mode > RELATIVE_MODE) {
+        return Err(VmError::InvalidParameterMode { pc, mode });
+    }
+    if id >= 100_000 {
+        return Err(VmError::InvalidParameterMode {
+            pc,
+            mode: id / 100_000,
+        });
+    }
+    Ok(Instruction {
+        op_code,
+        third_parameter_mode: modes[2],
+        second_parameter_mode: modes[1],
+        first_parameter_mode: modes[0],
+    })
+}
+
+pub fn parse_program(source: &str) -> Result<Vec<i64>, std::num::ParseIntError> {
+    source.split(",").map(|s| s.trim().parse::<i64>()).collect()
+}
+
+#[derive(Debug, Clone)]
+pub struct Vm {
+    memory: Vec<i64>,
+    pc: usize,
+    relative_base: i64,
+    input: VecDeque<i64>,
+    session: Option<Session>,
+    steps_left: Option<u64>,
+}
+
+impl Vm {
+    pub fn new(program: Vec<i64>) -> Self {
+        Vm {
+            memory: program,
+            pc: 0,
+            relative_base: 0,
+            input: VecDeque::new(),
+            session: None,
+            steps_left: None,
+        }
+    }
+
+    /// Stops a runaway program after `limit` more instructions with
+    /// `VmError::StepLimitExceeded` instead of looping forever.
+    pub fn set_step_limit(&mut self, limit: u64) {
+        self.steps_left = Some(limit);
+    }
+
+    /// Starts recording every consumed input and produced output.
+    pub fn record(&mut self) {
+        self.session.get_or_insert_with(Session::new);
+    }
+
+    pub fn session(&self) -> Option<&Session> {
+        self.session.as_ref()
+    }
+
+    pub fn take_session(&mut self) -> Option<Session> {
+        self.session.take()
+    }
+
+    pub fn memory(&self) -> &[i64] {
+        &self.memory
+    }
+
+    pub fn push_input(&mut self, value: i64) {
+        self.input.push_back(value);
+    }
+
+    /// Runs until the program halts, produces an output or needs an input
+    /// that has not been pushed yet. Calling `run` again resumes where it
+    /// stopped.
+    pub fn run(&mut self) -> Result<State, VmError> {
+        loop {
+            if self.pc >= self.memory.len() {
+                // Running off the end of the program counts as a halt, just
+                // like the `while pc < source_code.len()` loops of the days.
+                return Ok(State::Halted);
+            }
+            let pc = self.pc;
+            if let Some(steps_left) = &mut self.steps_left {
+                if *steps_left == 0 {
+                    return Err(VmError::StepLimitExceeded { pc });
+                }
+                *steps_left -= 1;
+            }
+            let instruction = decode(pc, self.memory[pc])?;
+            let mut next_pc = pc + instruction.op_code.cycles as usize;
+
+            match instruction.op_code {
+                ADD => {
+                    let lhs = self.read(1, instruction.first_parameter_mode)?;
+                    let rhs = self.read(2, instruction.second_parameter_mode)?;
+                    let value = lhs.checked_add(rhs).ok_or(VmError::Overflow { pc })?;
+                    self.write(3, instruction.third_parameter_mode, value)?;
+                }
+                MULTIPLY => {
+                    let lhs = self.read(1, instruction.first_parameter_mode)?;
+                    let rhs = self.read(2, instruction.second_parameter_mode)?;
+                    let value = lhs.checked_mul(rhs).ok_or(VmError::Overflow { pc })?;
+                    self.write(3, instruction.third_parameter_mode, value)?;
+                }
+                INPUT => {
+                    let Some(value) = self.input.pop_front() else {
+                        return Ok(State::NeedsInput);
+                    };
+                    if let Some(session) = &mut self.session {
+                        session.push(Event::Input(value));
+                    }
+                    self.write(1, instruction.first_parameter_mode, value)?;
+                }
+                OUTPUT => {
+                    let value = self.read(1, instruction.first_parameter_mode)?;
+                    if let Some(session) = &mut self.session {
+                        session.push(Event::Output(value));
+                    }
+                    self.pc = next_pc;
+                    return Ok(State::Output(value));
+                }
+                JUMP_IF_TRUE => {
+                    if self.read(1, instruction.first_parameter_mode)? != 0 {
+                        next_pc = self.jump_target(instruction.second_parameter_mode)?;
+                    }
+                }
+                JUMP_IF_FALSE => {
+                    if self.read(1, instruction.first_parameter_mode)? == 0 {
+                        next_pc = self.jump_target(instruction.second_parameter_mode)?;
+                    }
+                }
+                LESS_THAN => {
+                    let lhs = self.read(1, instruction.first_parameter_mode)?;
+                    let rhs = self.read(2, instruction.second_parameter_mode)?;
+                    self.write(3, instruction.third_parameter_mode, (lhs < rhs) as i64)?;
+                }
+                EQUALS => {
+                    let lhs = self.read(1, instruction.first_parameter_mode)?;
+                    let rhs = self.read(2, instruction.second_parameter_mode)?;
+                    self.write(3, instruction.third_parameter_mode, (lhs == rhs) as i64)?;
+                }
+                ADJUST_RELATIVE_BASE => {
+                    let offset = self.read(1, instruction.first_parameter_mode)?;
+                    self.relative_base = self
+                        .relative_base
+                        .checked_add(offset)
+                        .ok_or(VmError::Overflow { pc })?;
+                }
+                HALT => {
+                    return Ok(State::Halted);
+                }
+                _ => unreachable!("decode only returns known opcodes"),
+            }
+            self.pc = next_pc;
+        }
+    }
+
+    /// Feeds `inputs` up front and runs to completion, collecting every
+    /// output. Needing more input than given is an error.
+    pub fn run_to_halt(&mut self, inputs: &[i64]) -> Result<Vec<i64>, VmError> {
+        self.input.extend(inputs);
+        let mut outputs = Vec::new();
+        loop {
+            match self.run()? {
+                State::Output(value) => outputs.push(value),
+                State::NeedsInput => return Err(VmError::InputExhausted { pc: self.pc }),
+                State::Halted => return Ok(outputs),
+            }
+        }
+    }
+
+    fn checked_address(&self, address: i64) -> Result<usize, VmError> {
+        match usize::try_from(address) {
+            Ok(index) if index < MAX_MEMORY => Ok(index),
+            _ => Err(VmError::AddressOutOfRange {
+                pc: self.pc,
+                address,
+            }),
+        }
+    }
+
+    fn load(&self, address: i64) -> Result<i64, VmError> {
+        let index = self.checked_address(address)?;
+        Ok(self.memory.get(index).copied().unwrap_or(0))
+    }
+
+    fn parameter_address(&self, offset: usize, mode: i64) -> Result<i64, VmError> {
+        let raw = self.load((self.pc + offset) as i64)?;
+        match mode {
+            POSITION_MODE => Ok(raw),
+            RELATIVE_MODE => self
+                .relative_base
+                .checked_add(raw)
+                .ok_or(VmError::Overflow { pc: self.pc }),
+            _ => Err(VmError::ImmediateWrite { pc: self.pc }),
+        }
+    }
+
+    fn read(&self, offset: usize, mode: i64) -> Result<i64, VmError> {
+        if mode == IMMEDIATE_MODE {
+            return self.load((self.pc + offset) as i64);
+        }
+        let address = self.parameter_address(offset, mode)?;
+        self.load(address)
+    }
+
+    fn write(&mut self, offset: usize, mode: i64, value: i64) -> Result<(), VmError> {
+        let address = self.parameter_address(offset, mode)?;
+        let index = self.checked_address(address)?;
+        if index >= self.memory.len() {
+            self.memory.resize(index + 1, 0);
+        }
+        self.memory[index] = value;
+        Ok(())
+    }
+
+    fn jump_target(&self, mode: i64) -> Result<usize, VmError> {
+        let target = self.read(2, mode)?;
+        self.checked_address(target)
+    }
+}
@@ -0,0 +1,181 @@
+use crate::{State, Vm, VmError};
+use std::fmt;
+use std::fs;
+use std::io;
+
+/*
+Recorded I/O of one VM run, stored as text with one event per line:
+
+    # intcode session
+    < 5       input consumed by the program
+    > 42      output produced by the program
+
+Replaying feeds the recorded inputs to a fresh VM and requires the re-recorded
+session to be byte-identical to the original.
+*/
+
+const HEADER: &str = "# intcode session";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Input(i64),
+    Output(i64),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Session {
+    events: Vec<Event>,
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(io::Error),
+    Parse {
+        line: usize,
+        content: String,
+    },
+    Vm(VmError),
+    Mismatch {
+        line: usize,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Io(err) => write!(f, "{err}"),
+            ReplayError::Parse { line, content } => {
+                write!(f, "invalid session line {line}: {content:?}")
+            }
+            ReplayError::Vm(err) => write!(f, "replay failed: {err}"),
+            ReplayError::Mismatch {
+                line,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "replay diverged at line {line}: expected {expected:?}, got {actual:?}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<io::Error> for ReplayError {
+    fn from(err: io::Error) -> Self {
+        ReplayError::Io(err)
+    }
+}
+
+impl From<VmError> for ReplayError {
+    fn from(err: VmError) -> Self {
+        ReplayError::Vm(err)
+    }
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Session::default()
+    }
+
+    pub fn push(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    pub fn inputs(&self) -> impl Iterator<Item = i64> + '_ {
+        self.events.iter().filter_map(|event| match event {
+            Event::Input(value) => Some(*value),
+            Event::Output(_) => None,
+        })
+    }
+
+    pub fn outputs(&self) -> impl Iterator<Item = i64> + '_ {
+        self.events.iter().filter_map(|event| match event {
+            Event::Input(_) => None,
+            Event::Output(value) => Some(*value),
+        })
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ReplayError> {
+        let mut session = Session::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let parse_error = || ReplayError::Parse {
+                line: index + 1,
+                content: line.to_string(),
+            };
+            let (direction, value) = line.split_at(line.chars().next().map_or(0, char::len_utf8));
+            let value: i64 = value.trim().parse().map_err(|_| parse_error())?;
+            session.push(match direction {
+                "<" => Event::Input(value),
+                ">" => Event::Output(value),
+                _ => return Err(parse_error()),
+            });
+        }
+        Ok(session)
+    }
+
+    pub fn load(path: &str) -> Result<Self, ReplayError> {
+        Session::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    /// Re-runs `program` with the recorded inputs and checks that it behaves
+    /// exactly as recorded. Running out of recorded inputs ends the replay,
+    /// so sessions of interactive runs that were quit early replay too.
+    pub fn replay(&self, program: Vec<i64>) -> Result<(), ReplayError> {
+        let mut vm = Vm::new(program);
+        vm.record();
+        self.inputs().for_each(|value| vm.push_input(value));
+        let result = loop {
+            match vm.run() {
+                Ok(State::Output(_)) => {}
+                Ok(State::NeedsInput | State::Halted) => break Ok(()),
+                Err(err) => break Err(err),
+            }
+        };
+        let replayed = vm.take_session().unwrap_or_default();
+
+        let expected = self.to_string();
+        let actual = replayed.to_string();
+        let expected: Vec<&str> = expected.lines().collect();
+        let actual: Vec<&str> = actual.lines().collect();
+        if let Some(index) = (0..expected.len().max(actual.len()))
+            .find(|&index| expected.get(index) != actual.get(index))
+        {
+            return Err(ReplayError::Mismatch {
+                line: index + 1,
+                expected: expected.get(index).unwrap_or(&"<end>").to_string(),
+                actual: actual.get(index).unwrap_or(&"<end>").to_string(),
+            });
+        }
+        result?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{HEADER}")?;
+        for event in &self.events {
+            match event {
+                Event::Input(value) => writeln!(f, "< {value}")?,
+                Event::Output(value) => writeln!(f, "> {value}")?,
+            }
+        }
+        Ok(())
+    }
+}
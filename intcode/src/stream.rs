@@ -0,0 +1,32 @@
+use crate::{State, Vm, VmError};
+use futures::{pin_mut, Sink, SinkExt, Stream, StreamExt};
+
+impl Vm {
+    /// Runs the program to completion, awaiting `input` whenever it needs a
+    /// value and sending every output into `output`.
+    ///
+    /// Connecting machines with channels and running them on one executor
+    /// (`futures::executor::block_on` over `try_join_all`) replaces passing
+    /// signals around by hand, feedback loops included.
+    pub async fn run_async(
+        &mut self,
+        input: impl Stream<Item = i64>,
+        output: impl Sink<i64>,
+    ) -> Result<(), VmError> {
+        pin_mut!(input);
+        pin_mut!(output);
+        loop {
+            match self.run()? {
+                State::NeedsInput => match input.next().await {
+                    Some(value) => self.push_input(value),
+                    None => return Err(VmError::InputExhausted { pc: self.pc }),
+                },
+                State::Output(value) => output
+                    .send(value)
+                    .await
+                    .map_err(|_| VmError::OutputClosed { pc: self.pc })?,
+                State::Halted => return Ok(()),
+            }
+        }
+    }
+}
@@ -0,0 +1,78 @@
+/*
+day07's interpreter as it was before the amplifiers moved to the shared VM,
+trimmed to what the differential tests run. It behaves like the original: the
+same asserts, a panic on unknown opcodes and on opcodes of more than five
+digits, and i32 arithmetic that panics on overflow.
+*/
+
+/// The parameter at `address`, or what it points to in position mode.
+fn read(source_code: &[i32], address: usize, mode: u32) -> i32 {
+    let parameter = source_code[address];
+    if mode == 1 {
+        parameter
+    } else {
+        assert!(parameter >= 0);
+        source_code[parameter as usize]
+    }
+}
+
+/// Runs the program with `phase_setting` as the first input and
+/// `input_signal` as every later one. Returns the last output, -1 without any.
+pub fn run_program(mut source_code: Vec<i32>, phase_setting: i32, input_signal: i32) -> i32 {
+    let mut pc = 0;
+    let mut first_input = true;
+    let mut output_value = -1;
+
+    while pc < source_code.len() {
+        let id = source_code[pc];
+        assert!(id > 0);
+        assert!(id < 100_000, "more than 5 digits");
+        let id = id as u32;
+        let (first_mode, second_mode, third_mode) = (id / 100 % 10, id / 1000 % 10, id / 10000);
+
+        match id % 100 {
+            op @ (1 | 2 | 7 | 8) => {
+                assert_eq!(third_mode, 0);
+                let lhs = read(&source_code, pc + 1, first_mode);
+                let rhs = read(&source_code, pc + 2, second_mode);
+                let destination = source_code[pc + 3];
+                assert!(destination >= 0);
+                source_code[destination as usize] = match op {
+                    1 => lhs + rhs,
+                    2 => lhs * rhs,
+                    7 => i32::from(lhs < rhs),
+                    _ => i32::from(lhs == rhs),
+                };
+                pc += 4;
+            }
+            3 => {
+                let destination = source_code[pc + 1] as usize;
+                // First input is phase setting, second is input signal
+                source_code[destination] = if first_input {
+                    first_input = false;
+                    phase_setting
+                } else {
+                    input_signal
+                };
+                pc += 2;
+            }
+            4 => {
+                output_value = read(&source_code, pc + 1, first_mode);
+                pc += 2;
+            }
+            op @ (5 | 6) => {
+                let lhs = read(&source_code, pc + 1, first_mode);
+                let rhs = read(&source_code, pc + 2, second_mode);
+                if (lhs != 0) == (op == 5) {
+                    assert!(rhs >= 0);
+                    pc = rhs as usize;
+                } else {
+                    pc += 3;
+                }
+            }
+            99 => break,
+            _ => panic!("Unknown INSTRUCTION"),
+        }
+    }
+    output_value
+}
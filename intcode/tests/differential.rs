@@ -0,0 +1,219 @@
+mod day07;
+
+use intcode::Vm;
+use std::panic;
+
+/*
+Runs the interpreter copies of day02, day05 and day07 and the shared VM on
+the same random programs and compares what they compute. day07's copy was
+replaced by the shared VM in the crate itself and lives on in `day07/`.
+
+The programs are straight-line code followed by a HALT and a data area. Writes
+only go to the data area and jumps only go forward to an instruction, so every
+program terminates and never modifies itself.
+
+The old copies `assert!` and index out of bounds instead of returning errors,
+and their u32 / i32 arithmetic panics on overflow. A case where a copy panics
+is skipped; the VM itself must never panic.
+*/
+
+const CASES: usize = 2000;
+const DATA_CELLS: usize = 8;
+
+struct Rng(u64);
+
+impl Rng {
+    // xorshift64*
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn value(&mut self) -> i64 {
+        self.below(21) as i64 - 10
+    }
+}
+
+struct Generated {
+    program: Vec<i64>,
+    input_count: usize,
+}
+
+/// Instruction sizes for ops 1..=8, index 0 unused.
+const CYCLES: [usize; 9] = [0, 4, 4, 2, 2, 3, 3, 4, 4];
+
+/// `immediate` allows immediate mode for read parameters.
+fn generate(rng: &mut Rng, ops: &[i64], immediate: bool) -> Generated {
+    let count = 1 + rng.below(8);
+    let chosen: Vec<i64> = (0..count).map(|_| ops[rng.below(ops.len())]).collect();
+    let mut starts = Vec::new();
+    let mut code_len = 0;
+    for &op in &chosen {
+        starts.push(code_len);
+        code_len += CYCLES[op as usize];
+    }
+    let halt = code_len;
+    let data = halt + 1;
+
+    let mut program = Vec::new();
+    let mut input_count = 0;
+    for (index, &op) in chosen.iter().enumerate() {
+        let mut modes = [0i64; 2];
+        let mut parameters = Vec::new();
+        let reads = match op {
+            3 => 0,
+            4..=6 => 1,
+            _ => 2,
+        };
+        for mode in modes.iter_mut().take(reads) {
+            if immediate && rng.below(2) == 0 {
+                *mode = 1;
+                parameters.push(rng.value());
+            } else {
+                parameters.push((data + rng.below(DATA_CELLS)) as i64);
+            }
+        }
+        match op {
+            5 | 6 => {
+                // Forward jump target, always immediate.
+                modes[1] = 1;
+                let targets: Vec<usize> =
+                    starts[index + 1..].iter().copied().chain([halt]).collect();
+                parameters.push(targets[rng.below(targets.len())] as i64);
+            }
+            4 => {}
+            _ => parameters.push((data + rng.below(DATA_CELLS)) as i64),
+        }
+        if op == 3 {
+            input_count += 1;
+        }
+        program.push(op + 100 * modes[0] + 1000 * modes[1]);
+        program.extend(parameters);
+    }
+    program.push(99);
+    program.extend((0..DATA_CELLS).map(|_| rng.value().abs()));
+    Generated {
+        program,
+        input_count,
+    }
+}
+
+fn quietly<T>(f: impl FnOnce() -> T + panic::UnwindSafe) -> Option<T> {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(f).ok();
+    panic::set_hook(hook);
+    result
+}
+
+#[test]
+fn day02_matches_vm() {
+    let mut rng = Rng(0x0202_0202_0202_0202);
+    let mut compared = 0;
+    for _ in 0..CASES {
+        // day02 only knows position mode and unsigned values.
+        let program = generate(&mut rng, &[1, 2], false).program;
+
+        let mut vm = Vm::new(program.clone());
+        let vm_result = vm.run_to_halt(&[]);
+
+        let mut data: Vec<u32> = program.iter().map(|&value| value as u32).collect();
+        let Some(data) = quietly(move || {
+            day02::run_program(&mut data);
+            data
+        }) else {
+            continue;
+        };
+        assert!(vm_result.is_ok(), "{program:?}: {vm_result:?}");
+        let vm_memory: Vec<u32> = vm.memory().iter().map(|&value| value as u32).collect();
+        assert_eq!(data, vm_memory, "{program:?}");
+        compared += 1;
+    }
+    assert!(compared > CASES / 2, "only {compared} cases compared");
+}
+
+#[test]
+fn day05_matches_vm() {
+    let mut rng = Rng(0x0505_0505_0505_0505);
+    let mut compared = 0;
+    for _ in 0..CASES {
+        let generated = generate(&mut rng, &[1, 2, 3, 4, 5, 6, 7, 8], true);
+        let input_value = rng.value();
+        let program = generated.program;
+
+        let mut vm = Vm::new(program.clone());
+        let vm_result = vm.run_to_halt(&vec![input_value; generated.input_count]);
+
+        let old_program: Vec<i32> = program.iter().map(|&value| value as i32).collect();
+        let Some(outputs) = quietly(move || day05::run_program(old_program, input_value as i32))
+        else {
+            continue;
+        };
+        let vm_outputs = vm_result.unwrap_or_else(|err| panic!("{program:?}: {err}"));
+        let outputs: Vec<i64> = outputs.into_iter().map(i64::from).collect();
+        assert_eq!(outputs, vm_outputs, "{program:?}");
+        compared += 1;
+    }
+    assert!(compared > CASES / 2, "only {compared} cases compared");
+}
+
+#[test]
+fn day07_matches_vm() {
+    let mut rng = Rng(0x0707_0707_0707_0707);
+    let mut compared = 0;
+    for _ in 0..CASES {
+        let generated = generate(&mut rng, &[1, 2, 3, 4, 5, 6, 7, 8], true);
+        let (phase, signal) = (rng.value(), rng.value());
+        let program = generated.program;
+
+        // The first input is the phase setting, every later one the signal.
+        let mut inputs = vec![signal; generated.input_count];
+        if let Some(first) = inputs.first_mut() {
+            *first = phase;
+        }
+        let mut vm = Vm::new(program.clone());
+        let vm_result = vm.run_to_halt(&inputs);
+
+        let old_program: Vec<i32> = program.iter().map(|&value| value as i32).collect();
+        let Some(output) =
+            quietly(move || day07::run_program(old_program, phase as i32, signal as i32))
+        else {
+            continue;
+        };
+        let vm_outputs = vm_result.unwrap_or_else(|err| panic!("{program:?}: {err}"));
+        // The copy only keeps the last output, -1 without any.
+        assert_eq!(
+            i64::from(output),
+            vm_outputs.last().copied().unwrap_or(-1),
+            "{program:?}"
+        );
+        compared += 1;
+    }
+    assert!(compared > CASES / 2, "only {compared} cases compared");
+}
+
+/// A cheap stand-in for the fuzz target (`fuzz/`) that runs on stable.
+#[test]
+fn vm_returns_errors_on_garbage() {
+    let mut rng = Rng(0xdead_beef_dead_beef);
+    for _ in 0..CASES {
+        let len = rng.below(32);
+        let program: Vec<i64> = (0..len)
+            .map(|_| match rng.below(4) {
+                0 => rng.value(),
+                1 => (rng.below(3) * 100 + rng.below(3) * 1000 + rng.below(10)) as i64,
+                2 => rng.next() as i64,
+                _ => 99,
+            })
+            .collect();
+        let mut vm = Vm::new(program);
+        vm.set_step_limit(10_000);
+        let _ = vm.run_to_halt(&[rng.value(), rng.value()]);
+    }
+}
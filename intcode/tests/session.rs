@@ -0,0 +1,75 @@
+use intcode::{Event, ReplayError, Session, State, Vm};
+
+// Reads two numbers and outputs their sum, twice.
+const ADD_TWICE: [i64; 17] = [
+    3, 100, 3, 101, 1, 100, 101, 102, 4, 102, 1105, 1, 0, 99, 0, 0, 0,
+];
+
+fn record(inputs: &[i64]) -> Session {
+    let mut vm = Vm::new(ADD_TWICE.to_vec());
+    vm.record();
+    let mut inputs = inputs.iter();
+    loop {
+        match vm.run().unwrap() {
+            State::NeedsInput => match inputs.next() {
+                Some(&value) => vm.push_input(value),
+                None => break,
+            },
+            State::Output(_) => {}
+            State::Halted => break,
+        }
+    }
+    vm.take_session().unwrap()
+}
+
+#[test]
+fn records_inputs_and_outputs_in_order() {
+    let session = record(&[1, 2, 3, 4]);
+    assert_eq!(
+        session.events(),
+        &[
+            Event::Input(1),
+            Event::Input(2),
+            Event::Output(3),
+            Event::Input(3),
+            Event::Input(4),
+            Event::Output(7),
+        ]
+    );
+}
+
+#[test]
+fn saved_session_replays() {
+    let session = record(&[5, 6]);
+    let reloaded = Session::parse(&session.to_string()).unwrap();
+    assert_eq!(reloaded, session);
+    // The program still wants more input, the replay stops where the
+    // recording did.
+    reloaded.replay(ADD_TWICE.to_vec()).unwrap();
+}
+
+#[test]
+fn tampered_output_is_reported() {
+    let text = "# intcode session\n< 1\n< 2\n> 4\n";
+    let session = Session::parse(text).unwrap();
+    match session.replay(ADD_TWICE.to_vec()) {
+        Err(ReplayError::Mismatch {
+            line,
+            expected,
+            actual,
+        }) => {
+            assert_eq!(line, 4);
+            assert_eq!(expected, "> 4");
+            assert_eq!(actual, "> 3");
+        }
+        other => panic!("expected a mismatch, got {other:?}"),
+    }
+}
+
+#[test]
+fn rejects_unknown_events() {
+    assert!(matches!(
+        Session::parse("< 1\n? 2\n"),
+        Err(ReplayError::Parse { line: 2, .. })
+    ));
+}
@@ -0,0 +1,84 @@
+use intcode::{State, Vm, VmError};
+
+// Reads two numbers and outputs their sum.
+const ADD: [i64; 11] = [3, 100, 3, 101, 1, 100, 101, 102, 4, 102, 99];
+
+#[test]
+fn suspends_until_input_is_pushed_and_resumes() {
+    let mut vm = Vm::new(ADD.to_vec());
+    assert_eq!(vm.run(), Ok(State::NeedsInput));
+    // Asking again without input changes nothing.
+    assert_eq!(vm.run(), Ok(State::NeedsInput));
+    vm.push_input(2);
+    assert_eq!(vm.run(), Ok(State::NeedsInput));
+    vm.push_input(3);
+    assert_eq!(vm.run(), Ok(State::Output(5)));
+    assert_eq!(vm.run(), Ok(State::Halted));
+    assert_eq!(vm.run(), Ok(State::Halted));
+    assert_eq!(vm.memory()[100..103], [2, 3, 5]);
+}
+
+#[test]
+fn inputs_can_be_pushed_ahead() {
+    let mut vm = Vm::new(ADD.to_vec());
+    vm.push_input(20);
+    vm.push_input(22);
+    assert_eq!(vm.run(), Ok(State::Output(42)));
+
+    assert_eq!(Vm::new(ADD.to_vec()).run_to_halt(&[1, 1]), Ok(vec![2]));
+    assert_eq!(
+        Vm::new(ADD.to_vec()).run_to_halt(&[1]),
+        Err(VmError::InputExhausted { pc: 2 })
+    );
+}
+
+#[test]
+fn the_step_limit_stops_endless_loops() {
+    let mut forever = Vm::new(vec![1105, 1, 0]);
+    forever.set_step_limit(1000);
+    assert_eq!(forever.run(), Err(VmError::StepLimitExceeded { pc: 0 }));
+
+    // The halt counts too: five instructions fit into a limit of five.
+    let mut vm = Vm::new(ADD.to_vec());
+    vm.set_step_limit(5);
+    assert_eq!(vm.run_to_halt(&[1, 2]), Ok(vec![3]));
+    let mut vm = Vm::new(ADD.to_vec());
+    vm.set_step_limit(4);
+    assert_eq!(
+        vm.run_to_halt(&[1, 2]),
+        Err(VmError::StepLimitExceeded { pc: 10 })
+    );
+}
+
+#[test]
+fn bad_instructions_are_errors() {
+    let run = |program: Vec<i64>| Vm::new(program).run_to_halt(&[]);
+    assert_eq!(
+        run(vec![1, 0, 0, 0, 42]),
+        Err(VmError::UnknownOpcode { pc: 4, opcode: 42 })
+    );
+    assert_eq!(
+        run(vec![-1]),
+        Err(VmError::UnknownOpcode { pc: 0, opcode: -1 })
+    );
+    assert_eq!(
+        run(vec![301, 0, 0, 0]),
+        Err(VmError::InvalidParameterMode { pc: 0, mode: 3 })
+    );
+    assert_eq!(
+        run(vec![11101, 1, 1, 3]),
+        Err(VmError::ImmediateWrite { pc: 0 })
+    );
+    assert_eq!(
+        run(vec![1, -1, 0, 0]),
+        Err(VmError::AddressOutOfRange { pc: 0, address: -1 })
+    );
+    assert_eq!(
+        run(vec![1101, i64::MAX, 1, 0]),
+        Err(VmError::Overflow { pc: 0 })
+    );
+    assert_eq!(
+        VmError::UnknownOpcode { pc: 4, opcode: 42 }.to_string(),
+        "unknown opcode 42 at pc 4"
+    );
+}